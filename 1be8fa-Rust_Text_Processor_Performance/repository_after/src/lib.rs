@@ -1,34 +1,251 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
 use std::cmp::Reverse;
 
+/// Identifier for a document indexed via [`TextProcessor::add_document`].
+pub type DocId = String;
+
+/// Tokenizer behavior for [`TextProcessor`]: how raw text is split into the
+/// tokens that feed word counts, the document index, and search, so that
+/// accent variants, inflected forms, and short noise tokens can be
+/// normalized away before anything else sees them.
+#[derive(Debug, Clone)]
+pub struct TokenizerConfig {
+    /// Fold accented Latin letters to their base form (e.g. "café" -> "cafe")
+    /// so accent variants of a word count together.
+    pub fold_diacritics: bool,
+    /// Strip common English plural/verb suffixes (e.g. "running" -> "run",
+    /// "boxes" -> "box") so inflected forms count together.
+    pub stem: bool,
+    /// Tokens shorter than this, after folding and stemming, are dropped.
+    pub min_token_length: usize,
+}
+
+impl Default for TokenizerConfig {
+    fn default() -> Self {
+        Self {
+            fold_diacritics: true,
+            stem: false,
+            min_token_length: 1,
+        }
+    }
+}
+
 pub struct TextProcessor {
     pub word_counts: HashMap<String, usize>,
     pub stop_words: HashSet<String>,
+
+    /// Per-document term counts, keyed by document id.
+    documents: HashMap<DocId, HashMap<String, usize>>,
+    /// token -> set of documents containing it.
+    inverted_index: HashMap<String, HashSet<DocId>>,
+    /// Every indexed token, organized for typo-tolerant lookup.
+    bk_tree: BkTree,
+    /// How many documents, each word in [`Self::word_counts`] appeared in,
+    /// tracked by [`Self::process_document`] independently of
+    /// [`Self::add_document`]'s `documents`/`inverted_index`.
+    doc_counts: HashMap<String, usize>,
+    /// Number of documents passed to [`Self::process_document`].
+    total_docs: usize,
+    /// How [`Self::tokenize`] normalizes each token once [`Self::segmenter`]
+    /// has split raw text into word-like spans.
+    tokenizer: TokenizerConfig,
+    /// How [`Self::tokenize`] splits raw text into word-like spans before
+    /// normalization. Defaults to [`UnicodeTokenizer`]; swap in
+    /// [`DictionaryTokenizer`] (via [`Self::with_segmenter`]) for CJK text.
+    segmenter: Box<dyn Tokenizer>,
 }
 
 impl TextProcessor {
+    /// Equivalent to [`Self::with_tokenizer`] with [`TokenizerConfig::default`].
     pub fn new(stop_words: Vec<String>) -> Self {
+        Self::with_tokenizer(stop_words, TokenizerConfig::default())
+    }
+
+    /// Like [`Self::new`], but with an explicit [`TokenizerConfig`].
+    pub fn with_tokenizer(stop_words: Vec<String>, tokenizer: TokenizerConfig) -> Self {
+        Self::with_segmenter(stop_words, tokenizer, Box::new(UnicodeTokenizer))
+    }
+
+    /// Like [`Self::with_tokenizer`], but with an explicit word-segmentation strategy.
+    pub fn with_segmenter(
+        stop_words: Vec<String>,
+        tokenizer: TokenizerConfig,
+        segmenter: Box<dyn Tokenizer>,
+    ) -> Self {
+        let stop_words = stop_words
+            .into_iter()
+            .filter_map(|s| normalize_token(&tokenizer, &s))
+            .collect();
         Self {
             word_counts: HashMap::new(),
-            stop_words: stop_words.into_iter().map(|s| s.to_lowercase()).collect(),
+            stop_words,
+            documents: HashMap::new(),
+            inverted_index: HashMap::new(),
+            bk_tree: BkTree::new(),
+            doc_counts: HashMap::new(),
+            total_docs: 0,
+            tokenizer,
+            segmenter,
         }
     }
 
-    pub fn process_text(&mut self, text: &str) {
-        for word in text.split_whitespace() {
-            let cleaned = self.clean_word(word);
-            if cleaned.is_empty() {
+    /// Split `text` into normalized tokens: word-like spans per
+    /// [`Self::segmenter`], followed by lowercasing, diacritic folding, and
+    /// stemming per [`Self::tokenizer`].
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        self.segmenter
+            .segment(text)
+            .into_iter()
+            .filter_map(|raw| normalize_token(&self.tokenizer, raw))
+            .collect()
+    }
+
+    /// Index a document's text for later retrieval via [`TextProcessor::search`].
+    ///
+    /// Builds per-document term counts and an inverted index from each
+    /// surviving token to the documents it appears in. Re-adding the same
+    /// `id` overwrites that document's previous contents.
+    pub fn add_document(&mut self, id: impl Into<String>, text: &str) {
+        let id = id.into();
+
+        // Drop this document's old postings before re-indexing it.
+        if let Some(old_terms) = self.documents.remove(&id) {
+            for term in old_terms.keys() {
+                if let Some(docs) = self.inverted_index.get_mut(term) {
+                    docs.remove(&id);
+                }
+            }
+        }
+
+        let mut term_counts: HashMap<String, usize> = HashMap::new();
+        for token in self.tokenize(text) {
+            if self.is_stop_word(&token) {
+                continue;
+            }
+            *term_counts.entry(token).or_insert(0) += 1;
+        }
+
+        for term in term_counts.keys() {
+            if !self.inverted_index.contains_key(term) {
+                self.bk_tree.insert(term);
+            }
+            self.inverted_index
+                .entry(term.clone())
+                .or_default()
+                .insert(id.clone());
+        }
+
+        self.documents.insert(id, term_counts);
+    }
+
+    /// Typo-tolerant search over documents indexed with [`add_document`].
+    ///
+    /// Each query token is matched against the BK-tree for every indexed
+    /// token within `max_typos` edits; the posting lists of all matches are
+    /// unioned per query token, and a document's score is the sum, over
+    /// every (query token, matched indexed token) pair, of
+    /// `term_count_in_doc / (1 + edit_distance)`. Results are sorted by
+    /// descending score.
+    pub fn search(&self, query: &str, max_typos: u8) -> Vec<(DocId, f64)> {
+        let mut scores: HashMap<DocId, f64> = HashMap::new();
+
+        for token in self.tokenize(query) {
+            if self.is_stop_word(&token) {
+                continue;
+            }
+
+            for (matched_term, distance) in self.bk_tree.query(&token, max_typos as u32) {
+                let Some(doc_ids) = self.inverted_index.get(&matched_term) else {
+                    continue;
+                };
+                for doc_id in doc_ids {
+                    let count = self
+                        .documents
+                        .get(doc_id)
+                        .and_then(|terms| terms.get(&matched_term))
+                        .copied()
+                        .unwrap_or(0);
+                    let weight = count as f64 / (1.0 + distance as f64);
+                    *scores.entry(doc_id.clone()).or_insert(0.0) += weight;
+                }
+            }
+        }
+
+        let mut ranked: Vec<(DocId, f64)> = scores.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked
+    }
+
+    /// Rank documents indexed via [`add_document`] against `query_terms`
+    /// using TF-IDF: for a term in a document, `tf = count_in_doc /
+    /// total_terms_in_doc` and `idf = ln(N / (1 + df))`, where `N` is the
+    /// number of indexed documents and `df` is the number of documents
+    /// containing the term. A document's score is the sum of `tf * idf`
+    /// over `query_terms`. Results are sorted by descending score.
+    pub fn rank_documents(&self, query_terms: &[String]) -> Vec<(DocId, f64)> {
+        let n = self.documents.len() as f64;
+        let mut scores: HashMap<DocId, f64> = HashMap::new();
+
+        for term in query_terms {
+            let term = term.to_lowercase();
+            let Some(doc_ids) = self.inverted_index.get(&term) else {
                 continue;
+            };
+            let idf = (n / (1.0 + doc_ids.len() as f64)).ln();
+
+            for doc_id in doc_ids {
+                let terms = &self.documents[doc_id];
+                let total_terms: usize = terms.values().sum();
+                if total_terms == 0 {
+                    continue;
+                }
+                let tf = terms.get(&term).copied().unwrap_or(0) as f64 / total_terms as f64;
+                *scores.entry(doc_id.clone()).or_insert(0.0) += tf * idf;
             }
+        }
+
+        let mut ranked: Vec<(DocId, f64)> = scores.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked
+    }
+
+    /// The most *distinctive* words of a document: each term in `doc_id` is
+    /// scored by the same TF-IDF formula as [`rank_documents`], so common
+    /// words shared across the corpus are down-weighted relative to words
+    /// that stand out in this document.
+    pub fn get_top_words_tfidf(&self, doc_id: &str, n: usize) -> Vec<(String, f64)> {
+        let Some(terms) = self.documents.get(doc_id) else {
+            return Vec::new();
+        };
+        let total_terms: usize = terms.values().sum();
+        if total_terms == 0 {
+            return Vec::new();
+        }
+
+        let doc_count = self.documents.len() as f64;
+        let mut scored: Vec<(String, f64)> = terms
+            .iter()
+            .map(|(term, &count)| {
+                let df = self.inverted_index.get(term).map(|d| d.len()).unwrap_or(0);
+                let idf = (doc_count / (1.0 + df as f64)).ln();
+                let tf = count as f64 / total_terms as f64;
+                (term.clone(), tf * idf)
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(n);
+        scored
+    }
 
-            // Use lowercase once
-            let lower = cleaned.to_lowercase();
-            if self.is_stop_word(&lower) {
+    pub fn process_text(&mut self, text: &str) {
+        for token in self.tokenize(text) {
+            if self.is_stop_word(&token) {
                 continue;
             }
 
             // Use Entry API
-            *self.word_counts.entry(lower).or_insert(0) += 1;
+            *self.word_counts.entry(token).or_insert(0) += 1;
         }
     }
 
@@ -36,10 +253,66 @@ impl TextProcessor {
         self.stop_words.contains(word)
     }
 
-    fn clean_word(&self, word: &str) -> String {
-        let mut result = String::with_capacity(word.len());
-        result.extend(word.chars().filter(|c| c.is_alphanumeric()));
-        result
+    /// Like [`Self::process_text`], but also records, for each distinct
+    /// word in `text`, that it occurred in one more document. This builds
+    /// up [`Self::document_frequency`] and the corpus size used by
+    /// [`Self::get_top_words_tfidf_corpus`], separately from the raw
+    /// occurrence counts in [`Self::word_counts`].
+    pub fn process_document(&mut self, text: &str) {
+        let mut seen_in_doc: HashSet<String> = HashSet::new();
+        for token in self.tokenize(text) {
+            if self.is_stop_word(&token) {
+                continue;
+            }
+            *self.word_counts.entry(token.clone()).or_insert(0) += 1;
+            seen_in_doc.insert(token);
+        }
+
+        for token in seen_in_doc {
+            *self.doc_counts.entry(token).or_insert(0) += 1;
+        }
+        self.total_docs += 1;
+    }
+
+    /// How many documents passed to [`Self::process_document`] contained
+    /// `word` (after tokenization and stop-word filtering).
+    pub fn document_frequency(&self, word: &str) -> usize {
+        self.doc_counts.get(word).copied().unwrap_or(0)
+    }
+
+    /// The `n` words from [`Self::word_counts`] with the highest
+    /// corpus-wide TF-IDF score, where `tf` is a word's total count and
+    /// `idf = ln(total_docs / (1 + document_frequency))`. Unlike
+    /// [`Self::get_top_words_tfidf`] (which ranks one document's terms
+    /// against the corpus indexed via [`Self::add_document`]), this ranks
+    /// every word ever seen by [`Self::process_document`] against how
+    /// ubiquitous it is across those documents, so common words that slip
+    /// past the stop-word list are naturally down-weighted. Reuses the
+    /// same bounded min-heap selection as [`Self::get_top_words`].
+    pub fn get_top_words_tfidf_corpus(&self, n: usize) -> Vec<(String, f64)> {
+        let total_docs = self.total_docs as f64;
+        let mut heap: BinaryHeap<Reverse<ScoredWord<'_>>> = BinaryHeap::with_capacity(n);
+
+        for (word, &count) in &self.word_counts {
+            let df = self.document_frequency(word);
+            let score = count as f64 * (total_docs / (1.0 + df as f64)).ln();
+            let entry = ScoredWord { score, word };
+            if heap.len() < n {
+                heap.push(Reverse(entry));
+            } else if let Some(Reverse(min)) = heap.peek() {
+                if entry > *min {
+                    heap.pop();
+                    heap.push(Reverse(entry));
+                }
+            }
+        }
+
+        let mut top: Vec<(String, f64)> = heap
+            .into_iter()
+            .map(|Reverse(ScoredWord { score, word })| (word.clone(), score))
+            .collect();
+        top.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        top
     }
 
     /// Efficient top-N retrieval using a min-heap to avoid cloning entire HashMap
@@ -88,6 +361,507 @@ impl TextProcessor {
     }
 }
 
+/// A `(score, word)` pair ordered by `score` alone, so it can sit in a
+/// [`BinaryHeap`] for [`TextProcessor::get_top_words_tfidf_corpus`]'s
+/// bounded top-N selection despite `f64` not being `Ord`.
+struct ScoredWord<'a> {
+    score: f64,
+    word: &'a String,
+}
+
+impl PartialEq for ScoredWord<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score
+    }
+}
+
+impl Eq for ScoredWord<'_> {}
+
+impl PartialOrd for ScoredWord<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredWord<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.score
+            .partial_cmp(&other.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+/// A BK-tree over indexed tokens, keyed by Levenshtein distance, for
+/// typo-tolerant lookup.
+///
+/// Each node's children are keyed by their edit distance to that node, so a
+/// query for term `t` with tolerance `k` only needs to descend into edges
+/// whose label lies in `[d-k, d+k]`, where `d` is the distance from `t` to
+/// the current node (the triangle-inequality pruning invariant).
+#[derive(Default)]
+struct BkTree {
+    root: Option<Box<BkNode>>,
+}
+
+struct BkNode {
+    word: String,
+    children: HashMap<u32, Box<BkNode>>,
+}
+
+impl BkTree {
+    fn new() -> Self {
+        Self { root: None }
+    }
+
+    fn insert(&mut self, word: &str) {
+        let Some(root) = &mut self.root else {
+            self.root = Some(Box::new(BkNode {
+                word: word.to_string(),
+                children: HashMap::new(),
+            }));
+            return;
+        };
+
+        let mut node = root.as_mut();
+        loop {
+            let distance = levenshtein(word, &node.word);
+            if distance == 0 {
+                // Already indexed.
+                return;
+            }
+            node = node.children.entry(distance).or_insert_with(|| {
+                Box::new(BkNode {
+                    word: word.to_string(),
+                    children: HashMap::new(),
+                })
+            });
+            if node.word == word {
+                return;
+            }
+        }
+    }
+
+    /// Return every indexed word within `k` edits of `term`, paired with its
+    /// edit distance.
+    fn query(&self, term: &str, k: u32) -> Vec<(String, u32)> {
+        let mut matches = Vec::new();
+        if let Some(root) = &self.root {
+            Self::query_node(root, term, k, &mut matches);
+        }
+        matches
+    }
+
+    fn query_node(node: &BkNode, term: &str, k: u32, matches: &mut Vec<(String, u32)>) {
+        let d = levenshtein(term, &node.word);
+        if d <= k {
+            matches.push((node.word.clone(), d));
+        }
+
+        let lo = d.saturating_sub(k);
+        let hi = d + k;
+        for (&label, child) in &node.children {
+            if label >= lo && label <= hi {
+                Self::query_node(child, term, k, matches);
+            }
+        }
+    }
+}
+
+/// Splits raw text into word-like spans, before [`normalize_token`] lower-
+/// cases, folds diacritics, and stems each one. Implementations return
+/// slices of the input, so segmentation stays zero-copy.
+pub trait Tokenizer {
+    fn segment<'a>(&self, text: &'a str) -> Vec<&'a str>;
+}
+
+/// Default segmenter: Unicode word-boundary segmentation (see
+/// [`word_boundaries`]), suitable for whitespace-delimited scripts and
+/// character-by-character for CJK.
+pub struct UnicodeTokenizer;
+
+impl Tokenizer for UnicodeTokenizer {
+    fn segment<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        word_boundaries(text)
+    }
+}
+
+/// Log-probability assigned to a single CJK character with no dictionary
+/// entry, so [`DictionaryTokenizer::segment_cjk_run`]'s DP always has a
+/// route through out-of-vocabulary text instead of getting stuck.
+const SINGLE_CHAR_FALLBACK_LOG_PROB: f64 = -9.0;
+
+/// CJK word segmenter: splits maximal runs of CJK characters (see
+/// [`is_cjk`]) into dictionary words via maximum-probability DAG
+/// segmentation, falling back to single characters where the dictionary
+/// has no match. Any non-CJK text is delegated to [`word_boundaries`], so
+/// mixed CJK/Latin text tokenizes sensibly on both sides.
+pub struct DictionaryTokenizer {
+    dictionary: HashMap<String, u64>,
+    max_word_chars: usize,
+}
+
+impl DictionaryTokenizer {
+    /// Builds a segmenter from a word -> frequency dictionary. A word with
+    /// frequency 0 is treated as frequency 1 (a dictionary entry always
+    /// outscores the single-character fallback).
+    pub fn new(dictionary: HashMap<String, u64>) -> Self {
+        let max_word_chars = dictionary.keys().map(|w| w.chars().count()).max().unwrap_or(1).max(1);
+        Self {
+            dictionary,
+            max_word_chars,
+        }
+    }
+
+    /// Maximum-probability segmentation of one CJK run: builds a DAG where
+    /// an edge from char index `k` to `j` exists whenever `run[k..j]` is a
+    /// dictionary entry (or `j - k == 1`, the always-available
+    /// single-character fallback), then runs a Viterbi-style DP maximizing
+    /// the summed log-frequency (equivalently, the product of frequencies)
+    /// along the path from the start to the end of the run.
+    fn segment_cjk_run<'a>(&self, run: &'a str) -> Vec<&'a str> {
+        let boundaries: Vec<usize> = run
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(run.len()))
+            .collect();
+        let n = boundaries.len() - 1;
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let neg_inf = f64::NEG_INFINITY;
+        let mut best_score = vec![neg_inf; n + 1];
+        let mut best_prev = vec![0usize; n + 1];
+        best_score[0] = 0.0;
+
+        for j in 1..=n {
+            for k in j.saturating_sub(self.max_word_chars)..j {
+                if best_score[k] == neg_inf {
+                    continue;
+                }
+                let candidate = &run[boundaries[k]..boundaries[j]];
+                let score = if j - k == 1 {
+                    match self.dictionary.get(candidate) {
+                        Some(&freq) => (freq.max(1) as f64).ln(),
+                        None => SINGLE_CHAR_FALLBACK_LOG_PROB,
+                    }
+                } else {
+                    match self.dictionary.get(candidate) {
+                        Some(&freq) => (freq.max(1) as f64).ln(),
+                        None => continue,
+                    }
+                };
+                let total = best_score[k] + score;
+                if total > best_score[j] {
+                    best_score[j] = total;
+                    best_prev[j] = k;
+                }
+            }
+        }
+
+        let mut tokens = Vec::new();
+        let mut j = n;
+        while j > 0 {
+            let k = best_prev[j];
+            tokens.push(&run[boundaries[k]..boundaries[j]]);
+            j = k;
+        }
+        tokens.reverse();
+        tokens
+    }
+}
+
+impl Tokenizer for DictionaryTokenizer {
+    fn segment<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let mut tokens = Vec::new();
+        for (is_cjk_run, run) in cjk_runs(text) {
+            if is_cjk_run {
+                tokens.extend(self.segment_cjk_run(run));
+            } else {
+                tokens.extend(word_boundaries(run));
+            }
+        }
+        tokens
+    }
+}
+
+/// Partitions `text` into maximal runs that are entirely CJK or entirely
+/// non-CJK, in order, tagged with which kind each run is.
+fn cjk_runs(text: &str) -> Vec<(bool, &str)> {
+    let mut runs = Vec::new();
+    let mut start = 0usize;
+    let mut current: Option<bool> = None;
+
+    for (i, c) in text.char_indices() {
+        let this_is_cjk = is_cjk(c);
+        match current {
+            None => current = Some(this_is_cjk),
+            Some(flag) if flag != this_is_cjk => {
+                runs.push((flag, &text[start..i]));
+                start = i;
+                current = Some(this_is_cjk);
+            }
+            _ => {}
+        }
+    }
+    if let Some(flag) = current {
+        runs.push((flag, &text[start..]));
+    }
+    runs
+}
+
+/// Scans `text` for word tokens, treating runs of letters/digits as one
+/// token (with an embedded `'` or `-` kept as part of the word, so
+/// contractions like "don't" and hyphenated compounds like "well-known"
+/// survive as a single token) and emitting each CJK ideograph/kana
+/// character as its own single-character token, since such text carries no
+/// whitespace to delimit words.
+fn word_boundaries(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    let mut start: Option<usize> = None;
+
+    while let Some(&(i, c)) = chars.peek() {
+        if is_cjk(c) {
+            if let Some(s) = start.take() {
+                tokens.push(&text[s..i]);
+            }
+            let end = i + c.len_utf8();
+            tokens.push(&text[i..end]);
+            chars.next();
+            continue;
+        }
+
+        if c.is_alphanumeric() {
+            if start.is_none() {
+                start = Some(i);
+            }
+            chars.next();
+            continue;
+        }
+
+        if (c == '\'' || c == '-') && start.is_some() {
+            let mut lookahead = chars.clone();
+            lookahead.next();
+            if lookahead.peek().is_some_and(|&(_, next)| next.is_alphanumeric()) {
+                chars.next();
+                continue;
+            }
+        }
+
+        if let Some(s) = start.take() {
+            tokens.push(&text[s..i]);
+        }
+        chars.next();
+    }
+    if let Some(s) = start {
+        tokens.push(&text[s..]);
+    }
+    tokens
+}
+
+/// CJK Unified Ideographs, CJK Extension A, and Hiragana/Katakana — the
+/// ranges common real-world Chinese/Japanese text actually uses.
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32, 0x4E00..=0x9FFF | 0x3400..=0x4DBF | 0x3040..=0x30FF)
+}
+
+/// Folds common accented Latin letters to their unaccented base form — a
+/// hand-rolled substitute for "NFD-normalize, then drop combining marks"
+/// covering the Latin-1 Supplement / Latin Extended-A letters most
+/// real-world text actually uses.
+fn fold_diacritic(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ą' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' => 'e',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'į' => 'i',
+        'ñ' | 'ń' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ō' | 'ø' => 'o',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ß' => 's',
+        other => other,
+    }
+}
+
+/// Minimal English suffix stripping ("running"/"run", "boxes"/"box",
+/// "jumped"/"jump"). Deliberately conservative: suffixes only fire on
+/// tokens long enough that short words like "is" or "as" pass through
+/// untouched.
+fn stem(word: &str) -> String {
+    let chars: Vec<char> = word.chars().collect();
+    let len = chars.len();
+
+    if len > 4 && word.ends_with("ing") {
+        let mut stemmed = chars[..len - 3].to_vec();
+        undouble_final_consonant(&mut stemmed);
+        return stemmed.into_iter().collect();
+    }
+    if len > 4 && word.ends_with("ed") {
+        let mut stemmed = chars[..len - 2].to_vec();
+        undouble_final_consonant(&mut stemmed);
+        return stemmed.into_iter().collect();
+    }
+    if len > 3 && word.ends_with("ies") {
+        let mut stemmed: String = chars[..len - 3].iter().collect();
+        stemmed.push('y');
+        return stemmed;
+    }
+    if len > 4
+        && (word.ends_with("xes")
+            || word.ends_with("sses")
+            || word.ends_with("ches")
+            || word.ends_with("shes"))
+    {
+        return chars[..len - 2].iter().collect();
+    }
+    if len > 3 && word.ends_with('s') && !word.ends_with("ss") {
+        return chars[..len - 1].iter().collect();
+    }
+    word.to_string()
+}
+
+/// Drops a doubled final consonant left over from suffix stripping (e.g.
+/// "runn" -> "run" after "running" loses "ing"), so short verbs whose
+/// consonant is doubled before `-ing`/`-ed` still collapse to their root.
+fn undouble_final_consonant(chars: &mut Vec<char>) {
+    let n = chars.len();
+    if n >= 2 && chars[n - 1] == chars[n - 2] && !is_vowel(chars[n - 1]) {
+        chars.pop();
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// Lowercases `raw`, then applies diacritic folding and stemming per
+/// `config`, dropping tokens left shorter than `config.min_token_length`.
+fn normalize_token(config: &TokenizerConfig, raw: &str) -> Option<String> {
+    let lower = raw.to_lowercase();
+    let folded = if config.fold_diacritics {
+        lower.chars().map(fold_diacritic).collect()
+    } else {
+        lower
+    };
+    let normalized = if config.stem { stem(&folded) } else { folded };
+
+    if normalized.chars().count() < config.min_token_length {
+        return None;
+    }
+    Some(normalized)
+}
+
+/// Classic O(n*m) edit-distance computation between two strings, operating
+/// on `char`s so multi-byte UTF-8 sequences count as one edit.
+fn levenshtein(a: &str, b: &str) -> u32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut prev: Vec<u32> = (0..=b.len() as u32).collect();
+    let mut curr = vec![0u32; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i as u32;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+/// One word monitored by [`SpaceSavingCounter`]. Its true occurrence
+/// count is guaranteed to lie in `[count - error, count]`; if `count -
+/// error` exceeds the k-th largest such lower bound, the word is a
+/// guaranteed true heavy hitter rather than just a plausible one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpaceSavingEntry {
+    pub word: String,
+    pub count: usize,
+    pub error: usize,
+}
+
+/// Bounded-memory approximate top-K word counter using the Space-Saving
+/// (Misra-Gries heavy-hitters) algorithm: unlike [`TextProcessor::word_counts`],
+/// which grows with the number of distinct words ever seen, this tracks at
+/// most `capacity` words no matter how large or varied the input stream is,
+/// at the cost of turning exact counts into `[count - error, count]` bounds.
+pub struct SpaceSavingCounter {
+    capacity: usize,
+    counts: HashMap<String, usize>,
+    errors: HashMap<String, usize>,
+    /// Monitored words ordered by `(count, word)`, so the eviction
+    /// candidate (smallest count) is always `by_count.iter().next()`.
+    /// Exactly mirrors `counts` in size, keeping memory at O(capacity).
+    by_count: BTreeSet<(usize, String)>,
+}
+
+impl SpaceSavingCounter {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "Space-Saving capacity must be at least 1");
+        Self {
+            capacity,
+            counts: HashMap::new(),
+            errors: HashMap::new(),
+            by_count: BTreeSet::new(),
+        }
+    }
+
+    /// Records one occurrence of `word`, evicting the current
+    /// smallest-count monitored word if `word` is new and the monitor is
+    /// already at capacity.
+    pub fn observe(&mut self, word: &str) {
+        if let Some(&old_count) = self.counts.get(word) {
+            self.by_count.remove(&(old_count, word.to_string()));
+            let new_count = old_count + 1;
+            self.counts.insert(word.to_string(), new_count);
+            self.by_count.insert((new_count, word.to_string()));
+            return;
+        }
+
+        if self.counts.len() < self.capacity {
+            self.counts.insert(word.to_string(), 1);
+            self.errors.insert(word.to_string(), 0);
+            self.by_count.insert((1, word.to_string()));
+            return;
+        }
+
+        let &(min_count, ref min_word) = self
+            .by_count
+            .iter()
+            .next()
+            .expect("by_count holds one entry per monitored word, and capacity is at least 1");
+        let min_word = min_word.clone();
+        self.by_count.remove(&(min_count, min_word.clone()));
+        self.counts.remove(&min_word);
+        self.errors.remove(&min_word);
+
+        let new_count = min_count + 1;
+        self.counts.insert(word.to_string(), new_count);
+        self.errors.insert(word.to_string(), min_count);
+        self.by_count.insert((new_count, word.to_string()));
+    }
+
+    /// The up-to-`n` monitored words with the highest counts, each with
+    /// its `[count - error, count]` bound.
+    pub fn get_top_words(&self, n: usize) -> Vec<SpaceSavingEntry> {
+        self.by_count
+            .iter()
+            .rev()
+            .take(n)
+            .map(|(count, word)| SpaceSavingEntry {
+                word: word.clone(),
+                count: *count,
+                error: self.errors.get(word).copied().unwrap_or(0),
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,4 +911,241 @@ mod tests {
         let unique = processor.get_unique_words();
         assert!(unique.capacity() >= unique.len());
     }
+
+    #[test]
+    fn test_search_exact_match() {
+        let mut processor = TextProcessor::new(vec![]);
+        processor.add_document("doc1", "the quick brown fox");
+        processor.add_document("doc2", "a lazy dog sleeps");
+
+        let results = processor.search("fox", 0);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "doc1");
+    }
+
+    #[test]
+    fn test_search_typo_tolerant() {
+        let mut processor = TextProcessor::new(vec![]);
+        processor.add_document("doc1", "the quick brown fox");
+
+        // "fix" is one edit away from "fox".
+        let results = processor.search("fix", 1);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "doc1");
+
+        // With zero tolerance there should be no match.
+        assert!(processor.search("fix", 0).is_empty());
+    }
+
+    #[test]
+    fn test_search_re_indexing_replaces_old_postings() {
+        let mut processor = TextProcessor::new(vec![]);
+        processor.add_document("doc1", "apple banana");
+        processor.add_document("doc1", "cherry");
+
+        assert!(processor.search("apple", 0).is_empty());
+        assert_eq!(processor.search("cherry", 0)[0].0, "doc1");
+    }
+
+    #[test]
+    fn test_levenshtein_basic() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+
+    #[test]
+    fn test_diacritic_folding_merges_accent_variants() {
+        let mut processor = TextProcessor::new(vec![]);
+        processor.process_text("café");
+        processor.process_text("cafe");
+        assert_eq!(processor.get_word_count("cafe"), 2);
+    }
+
+    #[test]
+    fn test_stemming_merges_inflected_forms() {
+        let config = TokenizerConfig {
+            stem: true,
+            ..TokenizerConfig::default()
+        };
+        let mut processor = TextProcessor::with_tokenizer(vec![], config);
+        processor.process_text("running runs run");
+        assert_eq!(processor.get_word_count("run"), 3);
+    }
+
+    #[test]
+    fn test_min_token_length_drops_short_tokens() {
+        let config = TokenizerConfig {
+            min_token_length: 3,
+            ..TokenizerConfig::default()
+        };
+        let mut processor = TextProcessor::with_tokenizer(vec![], config);
+        processor.process_text("a an the cat sat");
+        assert_eq!(processor.get_word_count("a"), 0);
+        assert_eq!(processor.get_word_count("cat"), 1);
+    }
+
+    #[test]
+    fn test_contraction_and_hyphenated_word_kept_as_one_token() {
+        let mut processor = TextProcessor::new(vec![]);
+        processor.process_text("don't stop well-known facts");
+        assert_eq!(processor.get_word_count("don't"), 1);
+        assert_eq!(processor.get_word_count("well-known"), 1);
+    }
+
+    #[test]
+    fn test_cjk_characters_tokenized_individually() {
+        let mut processor = TextProcessor::new(vec![]);
+        processor.process_text("你好世界");
+        assert_eq!(processor.get_word_count("你"), 1);
+        assert_eq!(processor.get_word_count("好"), 1);
+        assert_eq!(processor.total_unique_words(), 4);
+    }
+
+    fn sample_dictionary() -> HashMap<String, u64> {
+        [("我", 500), ("爱", 300), ("北京", 800), ("天安门", 600)]
+            .into_iter()
+            .map(|(w, f)| (w.to_string(), f))
+            .collect()
+    }
+
+    #[test]
+    fn test_dictionary_tokenizer_prefers_known_multi_character_words() {
+        let mut processor = TextProcessor::with_segmenter(
+            vec![],
+            TokenizerConfig::default(),
+            Box::new(DictionaryTokenizer::new(sample_dictionary())),
+        );
+        processor.process_text("我爱北京天安门");
+        assert_eq!(processor.get_word_count("我"), 1);
+        assert_eq!(processor.get_word_count("爱"), 1);
+        assert_eq!(processor.get_word_count("北京"), 1);
+        assert_eq!(processor.get_word_count("天安门"), 1);
+        assert_eq!(processor.total_unique_words(), 4);
+    }
+
+    #[test]
+    fn test_dictionary_tokenizer_falls_back_to_single_characters_for_unknown_run() {
+        let mut processor = TextProcessor::with_segmenter(
+            vec![],
+            TokenizerConfig::default(),
+            Box::new(DictionaryTokenizer::new(sample_dictionary())),
+        );
+        // None of these characters (or pairs) are in the sample dictionary.
+        processor.process_text("你好");
+        assert_eq!(processor.get_word_count("你"), 1);
+        assert_eq!(processor.get_word_count("好"), 1);
+    }
+
+    #[test]
+    fn test_dictionary_tokenizer_delegates_non_cjk_runs_to_unicode_segmentation() {
+        let mut processor = TextProcessor::with_segmenter(
+            vec![],
+            TokenizerConfig::default(),
+            Box::new(DictionaryTokenizer::new(sample_dictionary())),
+        );
+        processor.process_text("I love 北京 today");
+        assert_eq!(processor.get_word_count("love"), 1);
+        assert_eq!(processor.get_word_count("北京"), 1);
+        assert_eq!(processor.get_word_count("today"), 1);
+    }
+
+    #[test]
+    fn test_rank_documents_favors_distinctive_term() {
+        let mut processor = TextProcessor::new(vec![]);
+        processor.add_document("common", "apple apple apple banana");
+        processor.add_document("rare", "apple cherry");
+
+        // "cherry" only appears in "rare", so it should outrank "common"
+        // even though "common" mentions "apple" more often.
+        let ranked = processor.rank_documents(&["cherry".to_string()]);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].0, "rare");
+    }
+
+    #[test]
+    fn test_get_top_words_tfidf_downweights_shared_terms() {
+        let mut processor = TextProcessor::new(vec![]);
+        processor.add_document("doc1", "apple apple apple banana");
+        processor.add_document("doc2", "apple banana banana banana");
+
+        // "apple" and "banana" appear in both documents (idf = ln(2/3) < 0),
+        // so apple's larger tf makes its score the most negative: it should
+        // not be the top term for doc1 even though it's the most frequent word.
+        let top = processor.get_top_words_tfidf("doc1", 1);
+        assert_eq!(top.len(), 1);
+        assert_ne!(top[0].0, "apple");
+    }
+
+    #[test]
+    fn test_process_document_tracks_total_and_document_frequency() {
+        let mut processor = TextProcessor::new(vec![]);
+        processor.process_document("apple apple banana");
+        processor.process_document("apple cherry");
+
+        assert_eq!(processor.get_word_count("apple"), 3);
+        assert_eq!(processor.document_frequency("apple"), 2);
+        assert_eq!(processor.document_frequency("banana"), 1);
+        assert_eq!(processor.document_frequency("cherry"), 1);
+        assert_eq!(processor.document_frequency("durian"), 0);
+    }
+
+    #[test]
+    fn test_get_top_words_tfidf_corpus_downweights_words_in_every_document() {
+        let mut processor = TextProcessor::new(vec![]);
+        processor.process_document("apple apple apple banana");
+        processor.process_document("apple banana banana banana");
+        processor.process_document("cherry cherry");
+
+        // "apple" and "banana" both appear in 2 of 3 documents (idf = 0),
+        // so despite having the highest raw counts they should be
+        // outranked by "cherry", which is confined to a single document.
+        let top = processor.get_top_words_tfidf_corpus(1);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].0, "cherry");
+    }
+
+    #[test]
+    fn test_space_saving_counter_exact_below_capacity() {
+        let mut counter = SpaceSavingCounter::new(10);
+        for word in ["apple", "banana", "apple", "apple", "banana"] {
+            counter.observe(word);
+        }
+
+        let top = counter.get_top_words(10);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].word, "apple");
+        assert_eq!(top[0].count, 3);
+        assert_eq!(top[0].error, 0);
+        assert_eq!(top[1].word, "banana");
+        assert_eq!(top[1].count, 2);
+        assert_eq!(top[1].error, 0);
+    }
+
+    #[test]
+    fn test_space_saving_counter_never_exceeds_capacity() {
+        let mut counter = SpaceSavingCounter::new(2);
+        for word in ["a", "b", "c", "d", "e"] {
+            counter.observe(word);
+        }
+        assert_eq!(counter.get_top_words(10).len(), 2);
+    }
+
+    #[test]
+    fn test_space_saving_counter_bounds_contain_the_true_heavy_hitter() {
+        let mut counter = SpaceSavingCounter::new(2);
+        // "frequent" is a true heavy hitter: it appears far more often than
+        // any of the many distinct one-off words competing for the other slot.
+        for _ in 0..100 {
+            counter.observe("frequent");
+        }
+        for i in 0..50 {
+            counter.observe(&format!("rare-{i}"));
+        }
+
+        let top = counter.get_top_words(1);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].word, "frequent");
+        // True count (100) must lie within [count - error, count].
+        assert!(top[0].count - top[0].error <= 100 && 100 <= top[0].count);
+    }
 }
@@ -1,19 +1,46 @@
-use actix_web::{error, web, App, HttpResponse, HttpServer, Responder, ResponseError};
+use actix_web::{error, web, App, HttpMessage, HttpRequest, HttpResponse, HttpServer, Responder, ResponseError};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use uuid::Uuid;
-use validator::{Validate, ValidationError};
+use validator::{Validate, ValidationError, ValidationErrors};
 use thiserror::Error;
+use actix_web::http::StatusCode;
+
+mod models;
+mod search;
+mod state;
+mod store;
+
+use models::{Book, CreateBook, UpdateBook};
+use search::RankRule;
+use state::AppState;
+use store::{BookStore, BookUpdate, InMemoryBookStore, SqliteBookStore};
 
 /* ===================== ERRORS ===================== */
 
+/// One failing field from a validation pass, coded so clients can branch on
+/// it without parsing English prose.
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldValidationError {
+    pub field: String,
+    pub code: String,
+    pub message: String,
+}
+
+/// `(code, error_type, status, link)` for one [`BookError`] variant, so every
+/// handler and the `JsonConfig` error handler produce consistent,
+/// programmatically-dispatchable errors.
+type ErrCode = (&'static str, &'static str, StatusCode, &'static str);
+
 #[derive(Debug, Error, Serialize)]
 pub enum BookError {
     #[error("Book not found")]
     NotFound,
-    #[error("Validation failed: {0}")]
-    ValidationError(String),
+    #[error("Validation failed: {message}")]
+    ValidationError {
+        message: String,
+        fields: Vec<FieldValidationError>,
+    },
     #[error("Immutable field update: {0}")]
     ImmutableUpdate(String),
     #[error("Internal server error")]
@@ -21,72 +48,94 @@ pub enum BookError {
 }
 
 impl BookError {
-    fn error_type(&self) -> &str {
-        match self {
-            BookError::NotFound => "NotFound",
-            BookError::ValidationError(_) => "ValidationError",
-            BookError::ImmutableUpdate(_) => "ImmutableUpdate",
-            BookError::InternalError => "InternalError",
+    /// A single, field-less validation failure (e.g. a malformed request
+    /// body we can't attribute to one field).
+    fn validation(message: impl Into<String>) -> Self {
+        let message = message.into();
+        BookError::ValidationError {
+            fields: vec![FieldValidationError {
+                field: "_".to_string(),
+                code: "invalid_request".to_string(),
+                message: message.clone(),
+            }],
+            message,
         }
     }
-}
 
-impl ResponseError for BookError {
-    fn error_response(&self) -> HttpResponse {
-        let status = match self {
-            BookError::NotFound => actix_web::http::StatusCode::NOT_FOUND,
-            BookError::ValidationError(_) => actix_web::http::StatusCode::BAD_REQUEST,
-            BookError::ImmutableUpdate(_) => actix_web::http::StatusCode::BAD_REQUEST,
-            BookError::InternalError => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-        };
-
-        HttpResponse::build(status).json(serde_json::json!({
-            "error": self.error_type(),
-            "message": self.to_string()
-        }))
+    fn validation_from_fields(fields: Vec<FieldValidationError>) -> Self {
+        let message = fields
+            .iter()
+            .map(|f| format!("{}: {}", f.field, f.message))
+            .collect::<Vec<_>>()
+            .join("; ");
+        BookError::ValidationError { message, fields }
     }
-}
-
-/* ===================== MODELS ===================== */
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Book {
-    id: Uuid,
-    title: String,
-    author: String,
-    price: f64,
-    stock: i64,
-}
-
-#[derive(Debug, Deserialize, Validate)]
-struct CreateBook {
-    #[validate(length(min = 1, message = "Title cannot be empty"))]
-    title: String,
-    #[validate(length(min = 1, message = "Author cannot be empty"))]
-    author: String,
-    #[validate(range(min = 0.01, message = "Price must be greater than 0"))]
-    price: f64,
-    #[validate(range(min = 0, message = "Stock must be 0 or greater"))]
-    stock: i64,
+    fn err_code(&self) -> ErrCode {
+        match self {
+            BookError::NotFound => (
+                "book_not_found",
+                "invalid_request",
+                StatusCode::NOT_FOUND,
+                "https://docs.example.com/bookstore/errors#book-not-found",
+            ),
+            BookError::ValidationError { .. } => (
+                "validation_failed",
+                "invalid_request",
+                StatusCode::BAD_REQUEST,
+                "https://docs.example.com/bookstore/errors#validation-failed",
+            ),
+            BookError::ImmutableUpdate(_) => (
+                "immutable_field",
+                "invalid_request",
+                StatusCode::BAD_REQUEST,
+                "https://docs.example.com/bookstore/errors#immutable-field",
+            ),
+            BookError::InternalError => (
+                "internal_error",
+                "internal",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "https://docs.example.com/bookstore/errors#internal-error",
+            ),
+        }
+    }
 }
 
-#[derive(Debug, Deserialize, Validate)]
-struct UpdateBook {
-    #[validate(length(min = 1, message = "Author cannot be empty"))]
-    author: Option<String>,
-    #[validate(range(min = 0.01, message = "Price must be greater than 0"))]
-    price: Option<f64>,
-    #[validate(range(min = 0, message = "Stock must be 0 or greater"))]
-    stock: Option<i64>,
-    // JSON fields to catch unauthorized updates
-    title: Option<serde_json::Value>,
-    id: Option<serde_json::Value>,
+impl From<ValidationErrors> for BookError {
+    fn from(errors: ValidationErrors) -> Self {
+        let fields: Vec<FieldValidationError> = errors
+            .field_errors()
+            .into_iter()
+            .flat_map(|(field, errs)| {
+                errs.iter().map(move |e: &ValidationError| FieldValidationError {
+                    field: field.to_string(),
+                    code: format!("invalid_{field}"),
+                    message: e
+                        .message
+                        .as_ref()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| format!("{field} is invalid")),
+                })
+            })
+            .collect();
+        BookError::validation_from_fields(fields)
+    }
 }
 
-/* ===================== STATE ===================== */
-
-struct AppState {
-    books: Arc<Mutex<HashMap<Uuid, Book>>>,
+impl ResponseError for BookError {
+    fn error_response(&self) -> HttpResponse {
+        let (code, error_type, status, link) = self.err_code();
+        let mut body = serde_json::json!({
+            "code": code,
+            "error_type": error_type,
+            "error_link": link,
+            "message": self.to_string(),
+        });
+        if let BookError::ValidationError { fields, .. } = self {
+            body["fields"] = serde_json::json!(fields);
+        }
+        HttpResponse::build(status).json(body)
+    }
 }
 
 /* ===================== HANDLERS ===================== */
@@ -96,9 +145,7 @@ async fn create_book(
     data: web::Data<AppState>,
     payload: web::Json<CreateBook>,
 ) -> Result<impl Responder, BookError> {
-    payload.validate().map_err(|e| BookError::ValidationError(e.to_string()))?;
-
-    let mut books = data.books.lock().map_err(|_| BookError::InternalError)?;
+    payload.validate()?;
 
     let book = Book {
         id: Uuid::new_v4(),
@@ -106,16 +153,24 @@ async fn create_book(
         author: payload.author.clone(),
         price: payload.price,
         stock: payload.stock,
+        categories: payload.categories.clone(),
     };
 
-    books.insert(book.id, book.clone());
+    data.store.create(book.clone())?;
     Ok(HttpResponse::Created().json(book))
 }
 
+#[derive(Debug, Deserialize)]
+struct ListQuery {
+    category: Option<String>,
+}
+
 // READ ALL
-async fn get_books(data: web::Data<AppState>) -> Result<impl Responder, BookError> {
-    let books = data.books.lock().map_err(|_| BookError::InternalError)?;
-    let list: Vec<Book> = books.values().cloned().collect();
+async fn get_books(
+    data: web::Data<AppState>,
+    query: web::Query<ListQuery>,
+) -> Result<impl Responder, BookError> {
+    let list = data.store.list(query.category.as_deref())?;
     Ok(HttpResponse::Ok().json(list))
 }
 
@@ -124,9 +179,7 @@ async fn get_book(
     data: web::Data<AppState>,
     id: web::Path<Uuid>,
 ) -> Result<impl Responder, BookError> {
-    let books = data.books.lock().map_err(|_| BookError::InternalError)?;
-
-    match books.get(&id.into_inner()) {
+    match data.store.get(id.into_inner())? {
         Some(book) => Ok(HttpResponse::Ok().json(book)),
         None => Err(BookError::NotFound),
     }
@@ -146,22 +199,242 @@ async fn update_book(
         return Err(BookError::ImmutableUpdate("id".into()));
     }
 
-    payload.validate().map_err(|e| BookError::ValidationError(e.to_string()))?;
+    payload.validate()?;
 
-    let mut books = data.books.lock().map_err(|_| BookError::InternalError)?;
-    let book = books.get_mut(&id.into_inner()).ok_or(BookError::NotFound)?;
+    let update = BookUpdate {
+        author: payload.author.clone(),
+        price: payload.price,
+        stock: payload.stock,
+    };
+
+    let book = data
+        .store
+        .update(id.into_inner(), update)?
+        .ok_or(BookError::NotFound)?;
+
+    Ok(HttpResponse::Ok().json(book))
+}
+
+// SEARCH
+#[derive(Debug, Deserialize)]
+struct SearchQuery {
+    q: String,
+    /// Comma-separated rule order, e.g. `proximity,words_matched`, overriding
+    /// the server's configured default for this request.
+    rules: Option<String>,
+}
+
+fn parse_rules(raw: &str) -> Option<Vec<RankRule>> {
+    raw.split(',')
+        .map(|r| match r.trim() {
+            "words_matched" => Some(RankRule::WordsMatched),
+            "typo_count" => Some(RankRule::TypoCount),
+            "proximity" => Some(RankRule::Proximity),
+            "exactness" => Some(RankRule::Exactness),
+            _ => None,
+        })
+        .collect()
+}
 
-    if let Some(author) = &payload.author {
-        book.author = author.clone();
+async fn search_books(
+    data: web::Data<AppState>,
+    query: web::Query<SearchQuery>,
+) -> Result<impl Responder, BookError> {
+    let rules = match &query.rules {
+        Some(raw) => {
+            parse_rules(raw).ok_or_else(|| BookError::validation("invalid rules"))?
+        }
+        None => data.search_rules.clone(),
+    };
+
+    let books = data.store.list(None)?;
+    // Title and author are searched together as one text per book.
+    let texts: Vec<String> = books
+        .iter()
+        .map(|b| format!("{} {}", b.title, b.author))
+        .collect();
+    let candidates: Vec<(usize, &str)> = texts
+        .iter()
+        .enumerate()
+        .map(|(i, t)| (i, t.as_str()))
+        .collect();
+
+    let ranked_indices = search::rank_books(candidates, &query.q, &rules);
+    let ranked: Vec<&Book> = ranked_indices.into_iter().map(|i| &books[i]).collect();
+
+    Ok(HttpResponse::Ok().json(ranked))
+}
+
+// BULK IMPORT / EXPORT
+
+#[derive(Debug, Serialize)]
+struct ImportRowError {
+    line: usize,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ImportSummary {
+    imported: usize,
+    failed: usize,
+    errors: Vec<ImportRowError>,
+}
+
+/// One numbered row from an import body: `Err` is a structural parse failure
+/// (bad JSON/CSV for that row), not yet a validation failure.
+type ImportRow = (usize, Result<CreateBook, String>);
+
+/// Parse a request body into `CreateBook` candidates, one per "row", numbered
+/// for error reporting. A `Err` candidate is a structural parse failure
+/// (bad JSON/CSV for that row); validation failures are handled uniformly by
+/// the caller once a candidate has been parsed.
+fn parse_import_rows(content_type: &str, body: &[u8]) -> Result<Vec<ImportRow>, BookError> {
+    match content_type {
+        "application/json" => {
+            let text = std::str::from_utf8(body)
+                .map_err(|e| BookError::validation(format!("invalid UTF-8: {e}")))?;
+            let books: Vec<CreateBook> = serde_json::from_str(text)
+                .map_err(|e| BookError::validation(format!("invalid JSON array: {e}")))?;
+            Ok(books.into_iter().enumerate().map(|(i, b)| (i + 1, Ok(b))).collect())
+        }
+        "application/x-ndjson" => {
+            let text = std::str::from_utf8(body)
+                .map_err(|e| BookError::validation(format!("invalid UTF-8: {e}")))?;
+            Ok(text
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(i, line)| {
+                    let row = serde_json::from_str::<CreateBook>(line).map_err(|e| e.to_string());
+                    (i + 1, row)
+                })
+                .collect())
+        }
+        "text/csv" => {
+            let mut reader = csv::Reader::from_reader(body);
+            Ok(reader
+                .deserialize::<CreateBook>()
+                .enumerate()
+                .map(|(i, row)| (i + 2, row.map_err(|e| e.to_string())))
+                .collect())
+        }
+        other => Err(BookError::validation(format!(
+            "unsupported Content-Type: {other}"
+        ))),
     }
-    if let Some(price) = payload.price {
-        book.price = price;
+}
+
+async fn import_books(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Bytes,
+) -> Result<impl Responder, BookError> {
+    let content_type = req.content_type().to_string();
+    let rows = parse_import_rows(&content_type, &body)?;
+
+    let mut summary = ImportSummary {
+        imported: 0,
+        failed: 0,
+        errors: Vec::new(),
+    };
+
+    for (line, row) in rows {
+        let candidate = match row {
+            Ok(candidate) => candidate,
+            Err(message) => {
+                summary.failed += 1;
+                summary.errors.push(ImportRowError { line, message });
+                continue;
+            }
+        };
+
+        if let Err(e) = candidate.validate() {
+            summary.failed += 1;
+            summary.errors.push(ImportRowError {
+                line,
+                message: e.to_string(),
+            });
+            continue;
+        }
+
+        let book = Book {
+            id: Uuid::new_v4(),
+            title: candidate.title,
+            author: candidate.author,
+            price: candidate.price,
+            stock: candidate.stock,
+            categories: candidate.categories,
+        };
+        data.store.create(book)?;
+        summary.imported += 1;
     }
-    if let Some(stock) = payload.stock {
-        book.stock = stock;
+
+    Ok(HttpResponse::Ok().json(summary))
+}
+
+#[derive(Debug, Deserialize)]
+struct ExportQuery {
+    format: String,
+}
+
+/// CSV row shape for [`Book`]: the `csv` crate can't serialize a `Vec` field
+/// inside a struct, so `categories` is flattened into a single
+/// semicolon-joined column here instead of on [`Book`] itself.
+#[derive(Debug, Serialize)]
+struct CsvBookRow {
+    id: Uuid,
+    title: String,
+    author: String,
+    price: f64,
+    stock: i64,
+    categories: String,
+}
+
+impl From<&Book> for CsvBookRow {
+    fn from(book: &Book) -> Self {
+        Self {
+            id: book.id,
+            title: book.title.clone(),
+            author: book.author.clone(),
+            price: book.price,
+            stock: book.stock,
+            categories: book.categories.join(";"),
+        }
     }
+}
 
-    Ok(HttpResponse::Ok().json(book.clone()))
+async fn export_books(
+    data: web::Data<AppState>,
+    query: web::Query<ExportQuery>,
+) -> Result<impl Responder, BookError> {
+    let mut values = data.store.list(None)?;
+    values.sort_by_key(|b| b.id);
+
+    match query.format.as_str() {
+        "jsonl" => {
+            let body = values
+                .iter()
+                .map(|b| serde_json::to_string(b).unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join("\n");
+            Ok(HttpResponse::Ok()
+                .content_type("application/x-ndjson")
+                .body(body))
+        }
+        "csv" => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            for book in &values {
+                writer
+                    .serialize(CsvBookRow::from(book))
+                    .map_err(|_| BookError::InternalError)?;
+            }
+            let body = writer.into_inner().map_err(|_| BookError::InternalError)?;
+            Ok(HttpResponse::Ok().content_type("text/csv").body(body))
+        }
+        other => Err(BookError::validation(format!(
+            "unsupported export format: {other}"
+        ))),
+    }
 }
 
 // DELETE
@@ -169,20 +442,38 @@ async fn delete_book(
     data: web::Data<AppState>,
     id: web::Path<Uuid>,
 ) -> Result<impl Responder, BookError> {
-    let mut books = data.books.lock().map_err(|_| BookError::InternalError)?;
-
-    match books.remove(&id.into_inner()) {
-        Some(_) => Ok(HttpResponse::NoContent().finish()),
-        None => Err(BookError::NotFound),
+    if data.store.delete(id.into_inner())? {
+        Ok(HttpResponse::NoContent().finish())
+    } else {
+        Err(BookError::NotFound)
     }
 }
 
 /* ===================== MAIN ===================== */
 
+/// Picks the storage backend from `BOOKSTORE_BACKEND` (`memory`, the
+/// default, or `sqlite`, reading/creating the database at `BOOKSTORE_DB_PATH`
+/// or `bookstore.db`).
+fn build_store() -> Arc<dyn BookStore> {
+    match std::env::var("BOOKSTORE_BACKEND").as_deref() {
+        Ok("sqlite") => {
+            let path = std::env::var("BOOKSTORE_DB_PATH").unwrap_or_else(|_| "bookstore.db".into());
+            let conn = rusqlite::Connection::open(&path)
+                .unwrap_or_else(|e| panic!("failed to open sqlite database at {path}: {e}"));
+            Arc::new(
+                SqliteBookStore::new(conn)
+                    .unwrap_or_else(|e| panic!("failed to initialize sqlite store: {e}")),
+            )
+        }
+        _ => Arc::new(InMemoryBookStore::new()),
+    }
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     let state = web::Data::new(AppState {
-        books: Arc::new(Mutex::new(HashMap::new())),
+        store: build_store(),
+        search_rules: search::default_rules(),
     });
 
     println!("Starting bookstore service on 0.0.0.0:8080...");
@@ -194,7 +485,7 @@ async fn main() -> std::io::Result<()> {
                 let err_msg = err.to_string();
                 error::InternalError::from_response(
                     err,
-                    BookError::ValidationError(err_msg).error_response(),
+                    BookError::validation(err_msg).error_response(),
                 )
                 .into()
             }))
@@ -202,6 +493,9 @@ async fn main() -> std::io::Result<()> {
                 web::scope("/books")
                     .route("", web::post().to(create_book))
                     .route("", web::get().to(get_books))
+                    .route("/search", web::get().to(search_books))
+                    .route("/import", web::post().to(import_books))
+                    .route("/export", web::get().to(export_books))
                     .route("/{id}", web::get().to(get_book))
                     .route("/{id}", web::patch().to(update_book))
                     .route("/{id}", web::delete().to(delete_book)),
@@ -225,6 +519,7 @@ mod tests {
             author: "Author".into(),
             price: 10.0,
             stock: 5,
+            categories: Vec::new(),
         };
         assert!(book.validate().is_err());
 
@@ -233,6 +528,7 @@ mod tests {
             author: "".into(),
             price: 10.0,
             stock: 5,
+            categories: Vec::new(),
         };
         assert!(book.validate().is_err());
 
@@ -241,6 +537,7 @@ mod tests {
             author: "Author".into(),
             price: -1.0,
             stock: 5,
+            categories: Vec::new(),
         };
         assert!(book.validate().is_err());
     }
@@ -278,4 +575,98 @@ mod tests {
         // This is handled in the handler, but the struct should allow these fields to exist
         assert!(book.title.is_some());
     }
+
+    #[test]
+    fn test_parse_import_rows_json_array() {
+        let body = br#"[{"title":"A","author":"B","price":1.0,"stock":1}]"#;
+        let rows = parse_import_rows("application/json", body).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].0, 1);
+        assert!(rows[0].1.is_ok());
+    }
+
+    #[test]
+    fn test_parse_import_rows_ndjson_skips_blank_lines() {
+        let body = b"{\"title\":\"A\",\"author\":\"B\",\"price\":1.0,\"stock\":1}\n\n{\"title\":\"C\",\"author\":\"D\",\"price\":2.0,\"stock\":2}\n";
+        let rows = parse_import_rows("application/x-ndjson", body).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|(_, r)| r.is_ok()));
+    }
+
+    #[test]
+    fn test_parse_import_rows_csv() {
+        let body = b"title,author,price,stock\nA,B,1.0,1\nC,D,2.0,2\n";
+        let rows = parse_import_rows("text/csv", body).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].0, 2);
+        assert_eq!(rows[1].0, 3);
+    }
+
+    #[test]
+    fn test_parse_import_rows_rejects_unknown_content_type() {
+        let result = parse_import_rows("text/plain", b"irrelevant");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_err_code_is_stable_per_variant() {
+        assert_eq!(BookError::NotFound.err_code().0, "book_not_found");
+        assert_eq!(BookError::InternalError.err_code().1, "internal");
+        assert_eq!(
+            BookError::ImmutableUpdate("title".into()).err_code().0,
+            "immutable_field"
+        );
+    }
+
+    #[test]
+    fn test_in_memory_store_filters_by_category() {
+        let store = InMemoryBookStore::new();
+        store
+            .create(Book {
+                id: Uuid::new_v4(),
+                title: "Dune".into(),
+                author: "Herbert".into(),
+                price: 9.99,
+                stock: 3,
+                categories: vec!["sci-fi".into()],
+            })
+            .unwrap();
+        store
+            .create(Book {
+                id: Uuid::new_v4(),
+                title: "Emma".into(),
+                author: "Austen".into(),
+                price: 6.5,
+                stock: 2,
+                categories: vec!["romance".into()],
+            })
+            .unwrap();
+
+        let scifi = store.list(Some("sci-fi")).unwrap();
+        assert_eq!(scifi.len(), 1);
+        assert_eq!(scifi[0].title, "Dune");
+
+        assert_eq!(store.list(None).unwrap().len(), 2);
+        assert!(store.list(Some("horror")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_validation_errors_split_per_field() {
+        let book = CreateBook {
+            title: "".into(),
+            author: "".into(),
+            price: -1.0,
+            stock: 5,
+            categories: Vec::new(),
+        };
+        let err: BookError = book.validate().unwrap_err().into();
+        match err {
+            BookError::ValidationError { fields, .. } => {
+                assert_eq!(fields.len(), 3);
+                assert!(fields.iter().any(|f| f.field == "title" && f.code == "invalid_title"));
+                assert!(fields.iter().any(|f| f.field == "price" && f.code == "invalid_price"));
+            }
+            other => panic!("expected ValidationError, got {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Book {
+    pub id: Uuid,
+    pub title: String,
+    pub author: String,
+    pub price: f64,
+    pub stock: i64,
+    #[serde(default)]
+    pub categories: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateBook {
+    #[validate(length(min = 1, message = "Title cannot be empty"))]
+    pub title: String,
+    #[validate(length(min = 1, message = "Author cannot be empty"))]
+    pub author: String,
+    #[validate(range(min = 0.01, message = "Price must be greater than 0"))]
+    pub price: f64,
+    #[validate(range(min = 0, message = "Stock must be 0 or greater"))]
+    pub stock: i64,
+    #[serde(default)]
+    pub categories: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateBook {
+    #[validate(length(min = 1, message = "Author cannot be empty"))]
+    pub author: Option<String>,
+    #[validate(range(min = 0.01, message = "Price must be greater than 0"))]
+    pub price: Option<f64>,
+    #[validate(range(min = 0, message = "Stock must be 0 or greater"))]
+    pub stock: Option<i64>,
+    // JSON fields to catch unauthorized updates
+    pub title: Option<serde_json::Value>,
+    pub id: Option<serde_json::Value>,
+}
@@ -0,0 +1,258 @@
+//! Text search and ranking over the book catalog.
+//!
+//! Titles (and authors) are split on whitespace into lowercased,
+//! alphanumeric-only words, then ranked by
+//! a configurable, ordered list of [`RankRule`]s applied as tie-breakers:
+//! each rule only reorders the books left tied by every earlier rule, so the
+//! rule order is authoritative.
+
+use serde::Deserialize;
+use std::cmp::Ordering;
+
+/// A single ranking criterion, applied in the order given to [`rank_books`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RankRule {
+    /// More query words matched ranks higher.
+    WordsMatched,
+    /// Fewer total typos across matched words ranks higher.
+    TypoCount,
+    /// Matched words closer together in the searched text ranks higher.
+    Proximity,
+    /// More exact-token matches (vs. prefix/fuzzy) ranks higher.
+    Exactness,
+}
+
+/// Default rule order, per the search endpoint's spec.
+pub fn default_rules() -> Vec<RankRule> {
+    vec![
+        RankRule::WordsMatched,
+        RankRule::TypoCount,
+        RankRule::Proximity,
+        RankRule::Exactness,
+    ]
+}
+
+/// Whitespace-split, lowercased, alphanumeric-only tokenization.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .filter_map(|word| {
+            let cleaned: String = word.chars().filter(|c| c.is_alphanumeric()).collect();
+            if cleaned.is_empty() {
+                None
+            } else {
+                Some(cleaned.to_lowercase())
+            }
+        })
+        .collect()
+}
+
+/// How well a single query word matched a single title word.
+struct WordMatch {
+    position: usize,
+    typos: u32,
+    exact: bool,
+}
+
+/// Aggregated match quality for one book against a query.
+#[derive(Debug, Clone, Copy)]
+struct MatchInfo {
+    words_matched: usize,
+    total_typos: u32,
+    proximity: usize,
+    exact_matches: usize,
+}
+
+/// Maximum edit distance considered a fuzzy match rather than a miss.
+const MAX_TYPOS: u32 = 2;
+
+fn best_match(title_words: &[(usize, &str)], query_word: &str) -> Option<WordMatch> {
+    title_words
+        .iter()
+        .filter_map(|&(position, word)| {
+            if word == query_word {
+                return Some(WordMatch {
+                    position,
+                    typos: 0,
+                    exact: true,
+                });
+            }
+            if word.starts_with(query_word) {
+                return Some(WordMatch {
+                    position,
+                    typos: 0,
+                    exact: false,
+                });
+            }
+            let distance = levenshtein(word, query_word);
+            if distance <= MAX_TYPOS {
+                Some(WordMatch {
+                    position,
+                    typos: distance,
+                    exact: false,
+                })
+            } else {
+                None
+            }
+        })
+        .min_by_key(|m| m.typos)
+}
+
+fn match_book(title: &str, query_words: &[String]) -> Option<MatchInfo> {
+    // tokenize() discards whitespace position, which proximity scoring
+    // needs, so pair cleaned words with their index directly here.
+    let cleaned_title_words: Vec<(usize, String)> = title
+        .split_whitespace()
+        .enumerate()
+        .filter_map(|(i, w)| {
+            let cleaned: String = w.chars().filter(|c| c.is_alphanumeric()).collect();
+            if cleaned.is_empty() {
+                None
+            } else {
+                Some((i, cleaned.to_lowercase()))
+            }
+        })
+        .collect();
+
+    let refs: Vec<(usize, &str)> = cleaned_title_words
+        .iter()
+        .map(|(i, w)| (*i, w.as_str()))
+        .collect();
+
+    let mut positions = Vec::new();
+    let mut total_typos = 0u32;
+    let mut exact_matches = 0usize;
+    let mut words_matched = 0usize;
+
+    for qw in query_words {
+        if let Some(m) = best_match(&refs, qw) {
+            words_matched += 1;
+            total_typos += m.typos;
+            if m.exact {
+                exact_matches += 1;
+            }
+            positions.push(m.position);
+        }
+    }
+
+    if words_matched == 0 {
+        return None;
+    }
+
+    let proximity = match (positions.iter().min(), positions.iter().max()) {
+        (Some(&lo), Some(&hi)) => hi - lo,
+        _ => 0,
+    };
+
+    Some(MatchInfo {
+        words_matched,
+        total_typos,
+        proximity,
+        exact_matches,
+    })
+}
+
+/// Compare two books' match quality according to a single rule. Higher
+/// "better" outcomes sort first (`Ordering::Less`).
+fn compare_rule(rule: RankRule, a: &MatchInfo, b: &MatchInfo) -> Ordering {
+    match rule {
+        RankRule::WordsMatched => b.words_matched.cmp(&a.words_matched),
+        RankRule::TypoCount => a.total_typos.cmp(&b.total_typos),
+        RankRule::Proximity => a.proximity.cmp(&b.proximity),
+        RankRule::Exactness => b.exact_matches.cmp(&a.exact_matches),
+    }
+}
+
+/// Rank `(id, title)` candidates against `query` using `rules` as an
+/// ordered list of tie-breakers: the first rule partitions the candidates,
+/// and each following rule only reorders within the bucket left tied by the
+/// rules before it.
+pub fn rank_books<'a, I, T>(candidates: I, query: &str, rules: &[RankRule]) -> Vec<T>
+where
+    I: IntoIterator<Item = (T, &'a str)>,
+    T: 'a,
+{
+    let query_words = tokenize(query);
+    if query_words.is_empty() {
+        return Vec::new();
+    }
+
+    let mut scored: Vec<(T, MatchInfo)> = candidates
+        .into_iter()
+        .filter_map(|(id, title)| match_book(title, &query_words).map(|info| (id, info)))
+        .collect();
+
+    scored.sort_by(|(_, a), (_, b)| {
+        for &rule in rules {
+            let ord = compare_rule(rule, a, b);
+            if ord != Ordering::Equal {
+                return ord;
+            }
+        }
+        Ordering::Equal
+    });
+
+    scored.into_iter().map(|(id, _)| id).collect()
+}
+
+fn levenshtein(a: &str, b: &str) -> u32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut prev: Vec<u32> = (0..=b.len() as u32).collect();
+    let mut curr = vec![0u32; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i as u32;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_match_beats_fuzzy_match() {
+        let candidates = vec![
+            ("fuzzy", "The Grate Gatsby"),
+            ("exact", "The Great Gatsby"),
+        ];
+        let ranked = rank_books(candidates, "great gatsby", &default_rules());
+        assert_eq!(ranked[0], "exact");
+    }
+
+    #[test]
+    fn more_words_matched_ranks_first() {
+        let candidates = vec![("one_word", "Great Expectations"), ("two_words", "Great Gatsby")];
+        let ranked = rank_books(candidates, "great gatsby", &default_rules());
+        assert_eq!(ranked[0], "two_words");
+    }
+
+    #[test]
+    fn no_match_is_excluded() {
+        let candidates = vec![("a", "Great Gatsby")];
+        let ranked = rank_books(candidates, "xyzzy", &default_rules());
+        assert!(ranked.is_empty());
+    }
+
+    #[test]
+    fn proximity_rule_ranks_tighter_match_first() {
+        // "Brief Encounter" vs "A Brief History Of Time Encounter" — the
+        // second has matched words further apart; with Proximity first it
+        // should lose to the tighter match even though word counts tie.
+        let candidates = vec![
+            ("far", "A Brief History Of Time Encounter"),
+            ("near", "Brief Encounter"),
+        ];
+        let rules = vec![RankRule::Proximity, RankRule::WordsMatched];
+        let ranked = rank_books(candidates, "brief encounter", &rules);
+        assert_eq!(ranked[0], "near");
+    }
+}
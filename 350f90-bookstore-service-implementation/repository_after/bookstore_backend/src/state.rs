@@ -1,10 +1,9 @@
-use crate::models::Book;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-use uuid::Uuid;
-
-pub type BookStore = Arc<Mutex<HashMap<Uuid, Book>>>;
+use crate::search::RankRule;
+use crate::store::BookStore;
+use std::sync::Arc;
 
 pub struct AppState {
-    pub books: BookStore,
+    pub store: Arc<dyn BookStore>,
+    /// Default tie-breaker order for `GET /books/search`, overridable per-request.
+    pub search_rules: Vec<RankRule>,
 }
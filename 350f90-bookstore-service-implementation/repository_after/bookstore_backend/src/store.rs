@@ -0,0 +1,329 @@
+//! Pluggable book storage.
+//!
+//! [`BookStore`] is the seam between handlers and persistence: handlers never
+//! touch a `HashMap` or a SQL connection directly, so the backend can be
+//! swapped at startup without touching `main.rs`'s routes. [`InMemoryBookStore`]
+//! is the default (and what the test suite exercises); [`SqliteBookStore`]
+//! persists to disk via `rusqlite` and additionally tracks categories in a
+//! normalized `categories` / `book_categories` join so books can be filtered
+//! by category without duplicating category rows.
+
+use crate::models::Book;
+use crate::BookError;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Partial update applied to a stored book; `None` fields are left untouched.
+pub struct BookUpdate {
+    pub author: Option<String>,
+    pub price: Option<f64>,
+    pub stock: Option<i64>,
+}
+
+pub trait BookStore: Send + Sync {
+    fn create(&self, book: Book) -> Result<(), BookError>;
+    fn get(&self, id: Uuid) -> Result<Option<Book>, BookError>;
+    /// All books, or only those tagged with `category` when given.
+    fn list(&self, category: Option<&str>) -> Result<Vec<Book>, BookError>;
+    fn update(&self, id: Uuid, update: BookUpdate) -> Result<Option<Book>, BookError>;
+    /// `true` if a book was removed, `false` if `id` didn't exist.
+    fn delete(&self, id: Uuid) -> Result<bool, BookError>;
+}
+
+/// The original `HashMap`-backed store, unchanged in behavior.
+pub struct InMemoryBookStore {
+    books: Mutex<HashMap<Uuid, Book>>,
+}
+
+impl InMemoryBookStore {
+    pub fn new() -> Self {
+        Self {
+            books: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryBookStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BookStore for InMemoryBookStore {
+    fn create(&self, book: Book) -> Result<(), BookError> {
+        let mut books = self.books.lock().map_err(|_| BookError::InternalError)?;
+        books.insert(book.id, book);
+        Ok(())
+    }
+
+    fn get(&self, id: Uuid) -> Result<Option<Book>, BookError> {
+        let books = self.books.lock().map_err(|_| BookError::InternalError)?;
+        Ok(books.get(&id).cloned())
+    }
+
+    fn list(&self, category: Option<&str>) -> Result<Vec<Book>, BookError> {
+        let books = self.books.lock().map_err(|_| BookError::InternalError)?;
+        Ok(books
+            .values()
+            .filter(|b| category.is_none_or(|c| b.categories.iter().any(|bc| bc == c)))
+            .cloned()
+            .collect())
+    }
+
+    fn update(&self, id: Uuid, update: BookUpdate) -> Result<Option<Book>, BookError> {
+        let mut books = self.books.lock().map_err(|_| BookError::InternalError)?;
+        let Some(book) = books.get_mut(&id) else {
+            return Ok(None);
+        };
+        if let Some(author) = update.author {
+            book.author = author;
+        }
+        if let Some(price) = update.price {
+            book.price = price;
+        }
+        if let Some(stock) = update.stock {
+            book.stock = stock;
+        }
+        Ok(Some(book.clone()))
+    }
+
+    fn delete(&self, id: Uuid) -> Result<bool, BookError> {
+        let mut books = self.books.lock().map_err(|_| BookError::InternalError)?;
+        Ok(books.remove(&id).is_some())
+    }
+}
+
+/// SQLite-backed store. Books live in `books`; categories are normalized
+/// into `categories` (one row per distinct name) with a `book_categories`
+/// join table, so tagging the same category on many books never duplicates
+/// it. `category_cache` mirrors `categories` in memory so repeat tags on the
+/// hot import/create path skip a round trip to look the id up.
+pub struct SqliteBookStore {
+    conn: Mutex<rusqlite::Connection>,
+    category_cache: Mutex<HashMap<String, i64>>,
+}
+
+fn sqlite_err(e: rusqlite::Error) -> BookError {
+    BookError::validation(format!("storage error: {e}"))
+}
+
+fn get_by_id(conn: &rusqlite::Connection, id: Uuid) -> Result<Option<Book>, BookError> {
+    let book = conn.query_row(
+        "SELECT title, author, price, stock FROM books WHERE id = ?1",
+        rusqlite::params![id.to_string()],
+        |row| {
+            Ok(Book {
+                id,
+                title: row.get(0)?,
+                author: row.get(1)?,
+                price: row.get(2)?,
+                stock: row.get(3)?,
+                categories: Vec::new(),
+            })
+        },
+    );
+
+    match book {
+        Ok(mut book) => {
+            book.categories = categories_for(conn, id)?;
+            Ok(Some(book))
+        }
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(sqlite_err(e)),
+    }
+}
+
+fn categories_for(conn: &rusqlite::Connection, id: Uuid) -> Result<Vec<String>, BookError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT c.name FROM categories c
+             JOIN book_categories bc ON bc.category_id = c.id
+             WHERE bc.book_id = ?1
+             ORDER BY c.name",
+        )
+        .map_err(sqlite_err)?;
+    let names = stmt
+        .query_map(rusqlite::params![id.to_string()], |row| row.get::<_, String>(0))
+        .map_err(sqlite_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(sqlite_err)?;
+    Ok(names)
+}
+
+impl SqliteBookStore {
+    /// Opens (creating if needed) the `books` / `categories` / `book_categories`
+    /// tables on `conn` and primes the category cache from what's on disk.
+    pub fn new(conn: rusqlite::Connection) -> Result<Self, BookError> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS books (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                author TEXT NOT NULL,
+                price REAL NOT NULL,
+                stock INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS categories (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE
+            );
+            CREATE TABLE IF NOT EXISTS book_categories (
+                book_id TEXT NOT NULL REFERENCES books(id),
+                category_id INTEGER NOT NULL REFERENCES categories(id),
+                PRIMARY KEY (book_id, category_id)
+            );",
+        )
+        .map_err(sqlite_err)?;
+
+        let mut category_cache = HashMap::new();
+        {
+            let mut stmt = conn
+                .prepare("SELECT id, name FROM categories")
+                .map_err(sqlite_err)?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+                .map_err(sqlite_err)?;
+            for row in rows {
+                let (id, name) = row.map_err(sqlite_err)?;
+                category_cache.insert(name, id);
+            }
+        }
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            category_cache: Mutex::new(category_cache),
+        })
+    }
+
+    /// Looks up `name`'s id, inserting a new category row (and caching it)
+    /// the first time it's seen.
+    fn category_id(&self, conn: &rusqlite::Connection, name: &str) -> Result<i64, BookError> {
+        let mut cache = self.category_cache.lock().map_err(|_| BookError::InternalError)?;
+        if let Some(&id) = cache.get(name) {
+            return Ok(id);
+        }
+
+        conn.execute(
+            "INSERT OR IGNORE INTO categories (name) VALUES (?1)",
+            rusqlite::params![name],
+        )
+        .map_err(sqlite_err)?;
+        let id: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = ?1",
+                rusqlite::params![name],
+                |row| row.get(0),
+            )
+            .map_err(sqlite_err)?;
+        cache.insert(name.to_string(), id);
+        Ok(id)
+    }
+}
+
+impl BookStore for SqliteBookStore {
+    fn create(&self, book: Book) -> Result<(), BookError> {
+        let conn = self.conn.lock().map_err(|_| BookError::InternalError)?;
+        conn.execute(
+            "INSERT INTO books (id, title, author, price, stock) VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![book.id.to_string(), book.title, book.author, book.price, book.stock],
+        )
+        .map_err(sqlite_err)?;
+
+        for category in &book.categories {
+            let category_id = self.category_id(&conn, category)?;
+            conn.execute(
+                "INSERT OR IGNORE INTO book_categories (book_id, category_id) VALUES (?1, ?2)",
+                rusqlite::params![book.id.to_string(), category_id],
+            )
+            .map_err(sqlite_err)?;
+        }
+        Ok(())
+    }
+
+    fn get(&self, id: Uuid) -> Result<Option<Book>, BookError> {
+        let conn = self.conn.lock().map_err(|_| BookError::InternalError)?;
+        get_by_id(&conn, id)
+    }
+
+    fn list(&self, category: Option<&str>) -> Result<Vec<Book>, BookError> {
+        let conn = self.conn.lock().map_err(|_| BookError::InternalError)?;
+
+        let ids: Vec<String> = match category {
+            Some(cat) => {
+                let mut stmt = conn
+                    .prepare(
+                        "SELECT b.id FROM books b
+                         JOIN book_categories bc ON bc.book_id = b.id
+                         JOIN categories c ON c.id = bc.category_id
+                         WHERE c.name = ?1",
+                    )
+                    .map_err(sqlite_err)?;
+                let rows = stmt
+                    .query_map(rusqlite::params![cat], |row| row.get::<_, String>(0))
+                    .map_err(sqlite_err)?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(sqlite_err)?;
+                rows
+            }
+            None => {
+                let mut stmt = conn.prepare("SELECT id FROM books").map_err(sqlite_err)?;
+                let rows = stmt
+                    .query_map([], |row| row.get::<_, String>(0))
+                    .map_err(sqlite_err)?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(sqlite_err)?;
+                rows
+            }
+        };
+
+        ids.into_iter()
+            .filter_map(|s| Uuid::parse_str(&s).ok())
+            .map(|id| get_by_id(&conn, id))
+            .filter_map(|r| r.transpose())
+            .collect()
+    }
+
+    fn update(&self, id: Uuid, update: BookUpdate) -> Result<Option<Book>, BookError> {
+        let conn = self.conn.lock().map_err(|_| BookError::InternalError)?;
+        if get_by_id(&conn, id)?.is_none() {
+            return Ok(None);
+        }
+
+        if let Some(author) = &update.author {
+            conn.execute(
+                "UPDATE books SET author = ?1 WHERE id = ?2",
+                rusqlite::params![author, id.to_string()],
+            )
+            .map_err(sqlite_err)?;
+        }
+        if let Some(price) = update.price {
+            conn.execute(
+                "UPDATE books SET price = ?1 WHERE id = ?2",
+                rusqlite::params![price, id.to_string()],
+            )
+            .map_err(sqlite_err)?;
+        }
+        if let Some(stock) = update.stock {
+            conn.execute(
+                "UPDATE books SET stock = ?1 WHERE id = ?2",
+                rusqlite::params![stock, id.to_string()],
+            )
+            .map_err(sqlite_err)?;
+        }
+
+        get_by_id(&conn, id)
+    }
+
+    fn delete(&self, id: Uuid) -> Result<bool, BookError> {
+        let conn = self.conn.lock().map_err(|_| BookError::InternalError)?;
+        conn.execute(
+            "DELETE FROM book_categories WHERE book_id = ?1",
+            rusqlite::params![id.to_string()],
+        )
+        .map_err(sqlite_err)?;
+        let changed = conn
+            .execute("DELETE FROM books WHERE id = ?1", rusqlite::params![id.to_string()])
+            .map_err(sqlite_err)?;
+        Ok(changed > 0)
+    }
+}
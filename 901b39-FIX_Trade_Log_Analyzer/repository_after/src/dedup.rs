@@ -0,0 +1,78 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Fixed-capacity, lock-free, direct-mapped cache of recently seen content
+/// hashes, used by [`crate::TradeAnalyzer`] to suppress retransmitted/replayed
+/// FIX messages.
+///
+/// Unlike the trade analyzer's own symbol table, this does *not* probe for a
+/// free slot on a collision: each
+/// hash maps to exactly one of `capacity` slots (`hash & (capacity - 1)`),
+/// and a new hash simply overwrites whatever previously lived there. That
+/// keeps memory fixed at construction time with no eviction bookkeeping,
+/// at the cost of perfect recall — two different hashes that alias to the
+/// same slot will evict each other, so the effective "window" of
+/// remembered messages shrinks as the stream's hash values collide. This
+/// is the "configurable eviction/window policy" the dedup stage needs: a
+/// caller picks `capacity` to trade memory for how far back duplicates are
+/// still caught.
+pub struct DedupCache {
+    slots: Box<[AtomicU64]>,
+}
+
+impl DedupCache {
+    /// `capacity` is rounded up to the next power of two (minimum 1).
+    pub fn new(capacity: usize) -> Self {
+        let cap = capacity.next_power_of_two().max(1);
+        let slots = (0..cap)
+            .map(|_| AtomicU64::new(0))
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+        Self { slots }
+    }
+
+    /// Records `hash`, returning `true` if that slot already held it (a
+    /// probable duplicate) or `false` if it was free or held something
+    /// else (a new message, which now occupies the slot). `0` is remapped
+    /// to `1` so it doesn't collide with an empty slot's sentinel value.
+    pub fn check_and_insert(&self, hash: u64) -> bool {
+        let hash = if hash == 0 { 1 } else { hash };
+        let slot = &self.slots[(hash as usize) & (self.slots.len() - 1)];
+        slot.swap(hash, Ordering::AcqRel) == hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeating_the_same_hash_is_reported_as_a_duplicate() {
+        let cache = DedupCache::new(8);
+        assert!(!cache.check_and_insert(42));
+        assert!(cache.check_and_insert(42));
+    }
+
+    #[test]
+    fn distinct_non_colliding_hashes_are_not_duplicates() {
+        let cache = DedupCache::new(8);
+        assert!(!cache.check_and_insert(1));
+        assert!(!cache.check_and_insert(2));
+        // Different slots (1 & 7 = 1, 2 & 7 = 2), so neither evicted the other.
+        assert!(cache.check_and_insert(1));
+        assert!(cache.check_and_insert(2));
+    }
+
+    #[test]
+    fn capacity_is_rounded_up_to_a_power_of_two() {
+        let cache = DedupCache::new(5);
+        assert_eq!(cache.slots.len(), 8);
+    }
+
+    #[test]
+    fn a_colliding_hash_evicts_the_previous_occupant_of_its_slot() {
+        let cache = DedupCache::new(1); // every hash maps to the single slot
+        assert!(!cache.check_and_insert(10));
+        assert!(!cache.check_and_insert(20)); // evicts 10
+        assert!(!cache.check_and_insert(10)); // 10 was evicted, so not a duplicate
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use crate::{find_unescaped, split_tag_value, FIELD_DELIM};
+
+/// A single field's decoded value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Str(String),
+    Int(i64),
+}
+
+impl Value {
+    fn parse(raw: &[u8]) -> Self {
+        let s = String::from_utf8_lossy(raw).to_string();
+        match s.parse::<i64>() {
+            Ok(n) => Value::Int(n),
+            Err(_) => Value::Str(s),
+        }
+    }
+
+    fn as_count(&self) -> u64 {
+        match self {
+            Value::Int(n) => (*n).max(0) as u64,
+            Value::Str(s) => s.parse::<u64>().unwrap_or(0),
+        }
+    }
+}
+
+/// One repetition within a repeating group.
+pub type FieldMap = HashMap<u16, Value>;
+
+/// Defines how to recognize one repeating group: the tag whose reappearance
+/// starts a new repetition (conventionally the group's first member tag),
+/// and the full set of tags that belong to it.
+pub struct GroupDef {
+    pub delimiter_tag: u16,
+    pub member_tags: &'static [u16],
+}
+
+/// Maps a count tag (e.g. `NoPartyIDs` / 453) to its group definition.
+pub struct GroupRegistry {
+    defs: HashMap<u16, GroupDef>,
+}
+
+impl GroupRegistry {
+    pub fn new() -> Self {
+        Self {
+            defs: HashMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, count_tag: u16, def: GroupDef) -> &mut Self {
+        self.defs.insert(count_tag, def);
+        self
+    }
+
+    /// A registry pre-populated with the common `NoPartyIDs` (453) and
+    /// `NoLegs` (555) groups.
+    pub fn with_defaults() -> Self {
+        let mut reg = Self::new();
+        reg.register(
+            453, // NoPartyIDs
+            GroupDef {
+                delimiter_tag: 448, // PartyID
+                member_tags: &[448, 447, 452],
+            },
+        );
+        reg.register(
+            555, // NoLegs
+            GroupDef {
+                delimiter_tag: 600, // LegSymbol
+                member_tags: &[600, 601, 623, 624],
+            },
+        );
+        reg
+    }
+}
+
+impl Default for GroupRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// In-progress state while consuming one repeating group's fields.
+struct GroupParseState {
+    count_tag: u16,
+    delimiter_tag: u16,
+    member_tags: &'static [u16],
+    target_count: u64,
+    instances: Vec<FieldMap>,
+    current: FieldMap,
+}
+
+/// A fully structural FIX message: flat top-level fields plus any repeating
+/// groups recognized via a [`GroupRegistry`].
+///
+/// Unlike [`crate::TradeAnalyzer::process_message`], this allocates per
+/// message and is meant for callers that need the full message structure
+/// (e.g. multi-party or multi-leg orders) rather than just trade statistics.
+pub struct FixMessage {
+    fields: FieldMap,
+    groups: HashMap<u16, Vec<FieldMap>>,
+}
+
+impl FixMessage {
+    /// Parses `raw`, consulting `registry` to recognize repeating groups.
+    /// Returns `None` if a field can't be split into `tag=value`, or a tag
+    /// isn't a valid decimal number.
+    pub fn parse(raw: &[u8], registry: &GroupRegistry) -> Option<Self> {
+        let mut fields = FieldMap::new();
+        let mut groups: HashMap<u16, Vec<FieldMap>> = HashMap::new();
+        let mut active: Option<GroupParseState> = None;
+
+        let mut i = 0usize;
+        while i < raw.len() {
+            let field_start = i;
+            let field_end = find_unescaped(raw, i, FIELD_DELIM);
+            i = match field_end {
+                Some(j) => j + 1,
+                None => raw.len(),
+            };
+            if field_start == i - 1 {
+                continue;
+            }
+
+            let field = &raw[field_start..field_end.unwrap_or(raw.len())];
+            let (tag_bytes, value_bytes) = split_tag_value(field)?;
+            let tag = parse_tag(tag_bytes)?;
+            let value = Value::parse(value_bytes);
+
+            // A field belonging to a just-closed group is reprocessed as if
+            // it were the next field in the message (new group, or flat).
+            let mut pending = Some((tag, value));
+            while let Some((tag, value)) = pending.take() {
+                let Some(state) = active.as_mut() else {
+                    if let Some(def) = registry.defs.get(&tag) {
+                        let target_count = value.as_count();
+                        fields.insert(tag, value);
+                        active = Some(GroupParseState {
+                            count_tag: tag,
+                            delimiter_tag: def.delimiter_tag,
+                            member_tags: def.member_tags,
+                            target_count,
+                            instances: Vec::new(),
+                            current: FieldMap::new(),
+                        });
+                    } else {
+                        fields.insert(tag, value);
+                    }
+                    continue;
+                };
+
+                let reps_so_far =
+                    state.instances.len() as u64 + u64::from(!state.current.is_empty());
+                if tag == state.delimiter_tag && reps_so_far < state.target_count {
+                    if !state.current.is_empty() {
+                        state.instances.push(std::mem::take(&mut state.current));
+                    }
+                    state.current.insert(tag, value);
+                } else if tag != state.delimiter_tag && state.member_tags.contains(&tag) {
+                    state.current.insert(tag, value);
+                } else {
+                    // Either a non-member tag, or the delimiter reappearing
+                    // after the group's declared count was already reached:
+                    // the group is done. Close it out and reprocess this
+                    // field as if the group were never active.
+                    if !state.current.is_empty() {
+                        state.instances.push(std::mem::take(&mut state.current));
+                    }
+                    let finished = active.take().unwrap();
+                    groups.insert(finished.count_tag, finished.instances);
+                    pending = Some((tag, value));
+                }
+            }
+        }
+
+        if let Some(state) = active.take() {
+            let mut instances = state.instances;
+            if !state.current.is_empty() {
+                instances.push(state.current);
+            }
+            groups.insert(state.count_tag, instances);
+        }
+
+        Some(Self { fields, groups })
+    }
+
+    pub fn field(&self, tag: u16) -> Option<&Value> {
+        self.fields.get(&tag)
+    }
+
+    /// The parsed repetitions of the repeating group keyed by `count_tag`
+    /// (e.g. `453` for `NoPartyIDs`), or an empty slice if the message
+    /// didn't carry that group.
+    pub fn group(&self, count_tag: u16) -> &[FieldMap] {
+        self.groups.get(&count_tag).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+fn parse_tag(tag: &[u8]) -> Option<u16> {
+    if tag.is_empty() {
+        return None;
+    }
+    let mut v: u32 = 0;
+    for &b in tag {
+        if !b.is_ascii_digit() {
+            return None;
+        }
+        v = v.checked_mul(10)?.checked_add((b - b'0') as u32)?;
+    }
+    u16::try_from(v).ok()
+}
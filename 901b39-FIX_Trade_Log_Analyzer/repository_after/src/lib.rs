@@ -1,7 +1,20 @@
+use std::collections::HashMap;
 use std::fmt;
 use std::io;
 use std::io::Write;
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+mod blake3;
+mod dedup;
+pub mod groups;
+pub mod order_book;
+mod queue;
+pub mod rules;
+pub mod session;
+
+use groups::GroupRegistry;
+use rules::{Diagnostic, FixRule};
 
 /// FIX messages here use `|` as the delimiter (instead of SOH).
 /// Values may contain a literal `|` escaped as `\|` (backslash escape).
@@ -30,6 +43,12 @@ pub enum ParseErrorKind {
     InvalidTimestamp,
     ArenaFull,
     TableFull,
+    /// `process_message_checked` only: the recomputed checksum (tag 10) did
+    /// not match the value carried in the message.
+    ChecksumMismatch { expected: u8, found: u8 },
+    /// `process_message_checked` only: the recomputed `BodyLength` (tag 9)
+    /// did not match the declared value.
+    BodyLengthMismatch { expected: usize, found: usize },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -46,6 +65,24 @@ impl fmt::Display for ParseError {
 
 impl std::error::Error for ParseError {}
 
+/// Returned by [`TradeAnalyzer::push`]: either the bounded ingestion ring
+/// is full, or `raw` is larger than the fixed per-slot buffer capacity set
+/// via [`TradeAnalyzer::new`].
+pub use queue::PushFrameError;
+
+impl fmt::Display for PushFrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            PushFrameError::Full => write!(f, "ingestion queue is full"),
+            PushFrameError::TooLarge { max, len } => {
+                write!(f, "frame of {len} bytes exceeds the {max}-byte slot capacity")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PushFrameError {}
+
 /// A view of a field value that may contain backslash escapes (notably `\|`).
 #[derive(Debug, Clone, Copy)]
 pub struct EscapedValue<'a> {
@@ -97,29 +134,133 @@ struct Parsed<'a> {
 pub struct TradeAnalyzer {
     total_messages: AtomicU64,
     malformed_messages: AtomicU64,
+    integrity_failures: AtomicU64,
     side_buy: AtomicU64,
     side_sell: AtomicU64,
     symbols: SymbolTable,
+    /// Checked before touching `rules`/`diagnostics` at all, so the hot path
+    /// costs one relaxed load when no rule has ever been registered.
+    rules_enabled: AtomicBool,
+    rules: Mutex<Vec<Box<dyn FixRule + Send + Sync>>>,
+    rule_registry: GroupRegistry,
+    diagnostics: Mutex<HashMap<String, Vec<Diagnostic>>>,
+    /// Bounded ingestion ring backing `push`/`drain_worker`; see those
+    /// methods. Unrelated to, and never touched by, `process_message`.
+    ///
+    /// Slots are reusable fixed-capacity byte buffers (see
+    /// [`queue::FrameRing`]), so feeding it via `push` and draining it via
+    /// `drain_worker` never allocates once warmed up — unlike a naive
+    /// `RingBuffer<Vec<u8>>`, which would allocate and free a `Vec` per frame.
+    queue: queue::FrameRing,
+    /// `None` when `TradeAnalyzer::new`'s `dedup_window` is `0`, so the
+    /// default, dedup-free hot path costs nothing beyond the `None` check.
+    dedup: Option<dedup::DedupCache>,
+    duplicates_suppressed: AtomicU64,
 }
 
 impl TradeAnalyzer {
     /// `max_symbols` bounds the number of distinct symbols that can be tracked.
+    /// `queue_capacity` bounds the number of raw frames buffered between
+    /// `push` and `drain_worker` (rounded up to the next power of two,
+    /// minimum 2).
+    /// `max_frame_bytes` bounds the size of any one frame `push` can accept.
     /// `arena_bytes` bounds the total bytes available to store unique symbol strings.
-    pub fn new(max_symbols: usize, arena_bytes: usize) -> Self {
+    /// `dedup_window` bounds the number of distinct content hashes the
+    /// retransmit-suppression cache remembers at once (rounded up to the
+    /// next power of two); `0` disables dedup entirely so it costs nothing
+    /// beyond a `None` check on the hot path.
+    pub fn new(
+        max_symbols: usize,
+        queue_capacity: usize,
+        max_frame_bytes: usize,
+        arena_bytes: usize,
+        dedup_window: usize,
+    ) -> Self {
         Self {
             total_messages: AtomicU64::new(0),
             malformed_messages: AtomicU64::new(0),
+            integrity_failures: AtomicU64::new(0),
             side_buy: AtomicU64::new(0),
             side_sell: AtomicU64::new(0),
             symbols: SymbolTable::new(max_symbols, arena_bytes),
+            rules_enabled: AtomicBool::new(false),
+            rules: Mutex::new(Vec::new()),
+            rule_registry: GroupRegistry::with_defaults(),
+            diagnostics: Mutex::new(HashMap::new()),
+            queue: queue::FrameRing::new(queue_capacity, max_frame_bytes),
+            dedup: (dedup_window > 0).then(|| dedup::DedupCache::new(dedup_window)),
+            duplicates_suppressed: AtomicU64::new(0),
         }
     }
 
+    /// Enqueues `raw` for later processing by `drain_worker` and returns
+    /// immediately, without parsing it on the caller's thread. This is the
+    /// fire-and-forget counterpart to `process_message`: a socket reader
+    /// thread can call this without ever blocking behind the stats/rules
+    /// locks `process_message` may take. Copies `raw` into a reusable slot
+    /// rather than allocating a new buffer per call.
+    pub fn push(&self, raw: &[u8]) -> Result<(), PushFrameError> {
+        self.queue.push(raw)
+    }
+
+    /// Drains and processes every frame currently buffered via `push`, via
+    /// `process_message_lossy` (so a malformed buffered frame is counted and
+    /// skipped rather than aborting the drain). Returns the number of
+    /// frames processed. A single call never blocks waiting for more
+    /// frames to arrive — call it repeatedly (e.g. in a loop on a dedicated
+    /// background thread) to keep draining as `push` produces more.
+    pub fn drain_worker(&self) -> usize {
+        let mut n = 0;
+        while self
+            .queue
+            .pop_with(|raw| self.process_message_lossy(raw, |_| {}))
+            .is_some()
+        {
+            n += 1;
+        }
+        n
+    }
+
+    /// Registers a validation rule to run during ingestion. The first call
+    /// flips the hot-path gate on: from then on, every `process_message`
+    /// call additionally re-parses the message into a [`groups::FixMessage`]
+    /// and runs all registered rules against it, collecting any
+    /// [`Diagnostic`]s into that message's symbol's report section.
+    pub fn register_rule(&self, rule: Box<dyn FixRule + Send + Sync>) {
+        self.rules.lock().unwrap().push(rule);
+        self.rules_enabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Diagnostics collected so far for `symbol`, in the order they were
+    /// raised. Empty if no rule has flagged anything for that symbol (or no
+    /// rule has been registered at all).
+    pub fn diagnostics_for(&self, symbol: &str) -> Vec<Diagnostic> {
+        self.diagnostics
+            .lock()
+            .unwrap()
+            .get(symbol)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     /// Parse and ingest one FIX message.
     ///
     /// Malformed messages are counted, optionally logged by the caller, and skipped.
+    /// If a dedup window was configured via `TradeAnalyzer::new`, a message
+    /// whose raw bytes hash to an entry already occupying its cache slot is
+    /// treated as a retransmit: it is counted in `duplicates_suppressed()`
+    /// and skipped before parsing, without returning an error.
     /// This function does **not** allocate on the hot path after warmup.
     pub fn process_message(&self, raw: &[u8]) -> Result<(), ParseError> {
+        if let Some(dedup) = &self.dedup {
+            let digest = blake3::hash(raw);
+            let hash_prefix = u64::from_le_bytes(digest[..8].try_into().unwrap());
+            if dedup.check_and_insert(hash_prefix) {
+                self.duplicates_suppressed.fetch_add(1, Ordering::Relaxed);
+                return Ok(());
+            }
+        }
+
         let parsed = parse_required(raw)?;
 
         self.total_messages.fetch_add(1, Ordering::Relaxed);
@@ -143,9 +284,39 @@ impl TradeAnalyzer {
         let _ = parsed.order_id;
         let _ = parsed.timestamp;
 
+        // Gated: with no rule registered this is a single relaxed load and
+        // nothing else, preserving the zero-allocation hot path.
+        if self.rules_enabled.load(Ordering::Relaxed) {
+            self.run_rules(raw, &parsed.symbol.to_string_lossy());
+        }
+
         Ok(())
     }
 
+    /// Re-parses `raw` into a [`groups::FixMessage`] and runs every
+    /// registered rule against it, filing any diagnostics under `symbol`.
+    /// Only reached once at least one rule is registered, so this
+    /// allocation never happens on the default, rule-free hot path.
+    fn run_rules(&self, raw: &[u8], symbol: &str) {
+        let Some(msg) = groups::FixMessage::parse(raw, &self.rule_registry) else {
+            return;
+        };
+        let rules = self.rules.lock().unwrap();
+        let mut found = Vec::new();
+        for rule in rules.iter() {
+            found.extend(rule.check(&msg));
+        }
+        drop(rules);
+        if !found.is_empty() {
+            self.diagnostics
+                .lock()
+                .unwrap()
+                .entry(symbol.to_string())
+                .or_default()
+                .extend(found);
+        }
+    }
+
     /// Like `process_message`, but never returns an error: it logs the error via `log_fn`.
     pub fn process_message_lossy<F: FnMut(ParseError)>(&self, raw: &[u8], mut log_fn: F) {
         if let Err(e) = self.process_message(raw) {
@@ -154,6 +325,34 @@ impl TradeAnalyzer {
         }
     }
 
+    /// Like `process_message`, but first verifies message integrity: the
+    /// checksum (tag 10) is recomputed and compared, and, when the message
+    /// carries a `BodyLength` (tag 9), that is recomputed and compared too.
+    /// Use this when the input isn't already trusted/pre-validated.
+    pub fn process_message_checked(&self, raw: &[u8]) -> Result<(), ParseError> {
+        verify_integrity(raw).map_err(|kind| ParseError { kind, at_byte: 0 })?;
+        self.process_message(raw)
+    }
+
+    /// Like `process_message_lossy`, but via `process_message_checked`.
+    /// Checksum/BodyLength mismatches are counted separately via
+    /// `integrity_failures()` rather than `malformed_messages()`, so
+    /// integrity failures can be distinguished from structural parse
+    /// failures.
+    pub fn process_message_checked_lossy<F: FnMut(ParseError)>(&self, raw: &[u8], mut log_fn: F) {
+        if let Err(e) = self.process_message_checked(raw) {
+            match e.kind {
+                ParseErrorKind::ChecksumMismatch { .. } | ParseErrorKind::BodyLengthMismatch { .. } => {
+                    self.integrity_failures.fetch_add(1, Ordering::Relaxed);
+                }
+                _ => {
+                    self.malformed_messages.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            log_fn(e);
+        }
+    }
+
     pub fn total_messages(&self) -> u64 {
         self.total_messages.load(Ordering::Relaxed)
     }
@@ -162,6 +361,24 @@ impl TradeAnalyzer {
         self.malformed_messages.load(Ordering::Relaxed)
     }
 
+    pub fn integrity_failures(&self) -> u64 {
+        self.integrity_failures.load(Ordering::Relaxed)
+    }
+
+    /// Messages skipped as probable retransmits; always `0` when
+    /// `TradeAnalyzer::new` was given a `dedup_window` of `0`.
+    pub fn duplicates_suppressed(&self) -> u64 {
+        self.duplicates_suppressed.load(Ordering::Relaxed)
+    }
+
+    /// The `p`-th percentile (`0.0..=1.0`) order quantity recorded for
+    /// `symbol`, or `None` if the symbol hasn't been seen. See
+    /// [`SymbolSlot::percentile`] for how this is derived from the
+    /// per-symbol histogram.
+    pub fn percentile(&self, symbol: &str, p: f64) -> Option<u64> {
+        self.symbols.percentile(symbol.as_bytes(), p)
+    }
+
     /// Streaming, non-blocking report generation: reads atomics and writes to `out`.
     pub fn write_report<W: Write>(&self, out: &mut W) -> io::Result<()> {
         writeln!(out, "=== Compliance Report ===")?;
@@ -172,7 +389,11 @@ impl TradeAnalyzer {
 
         writeln!(out, "\n=== Volume by Symbol ===")?;
         for s in self.symbols.snapshot() {
-            writeln!(out, "{} count={} volume={}", s.symbol, s.count, s.volume)?;
+            writeln!(
+                out,
+                "{} count={} volume={} p50={} p95={} p99={}",
+                s.symbol, s.count, s.volume, s.p50, s.p95, s.p99
+            )?;
         }
         Ok(())
     }
@@ -267,6 +488,84 @@ fn parse_required(raw: &[u8]) -> Result<Parsed<'_>, ParseError> {
     })
 }
 
+/// Verifies the checksum (tag 10) and, when present, the `BodyLength` (tag
+/// 9) of a raw message, without doing a full structural parse.
+///
+/// `BodyLength` is optional here: these fixtures don't always carry tag 9,
+/// so its check is skipped (not failed) when the tag is absent.
+fn verify_integrity(raw: &[u8]) -> Result<(), ParseErrorKind> {
+    let mut tag9: Option<(&[u8], usize)> = None; // (value, index of its delimiter)
+    let mut tag10: Option<(&[u8], usize)> = None; // (value, index of its field_start)
+
+    let mut i = 0usize;
+    while i < raw.len() {
+        let field_start = i;
+        let field_end = find_unescaped(raw, i, FIELD_DELIM);
+        i = match field_end {
+            Some(j) => j + 1,
+            None => raw.len(),
+        };
+        if field_start == i - 1 {
+            continue;
+        }
+
+        let field = &raw[field_start..field_end.unwrap_or(raw.len())];
+        let (tag, value) = match split_tag_value(field) {
+            Some(tv) => tv,
+            // Structural issues are reported by the regular parse path.
+            None => continue,
+        };
+
+        match tag {
+            b"9" => {
+                if let Some(delim) = field_end {
+                    tag9 = Some((value, delim));
+                }
+            }
+            b"10" => {
+                tag10 = Some((value, field_start));
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let (checksum_value, tag10_start) = tag10.ok_or(ParseErrorKind::MissingTag)?;
+    let expected_checksum =
+        parse_u64(checksum_value).map_err(|_| ParseErrorKind::InvalidNumber)? as u8;
+    let found_checksum = checksum_of(&raw[..tag10_start]);
+    if found_checksum != expected_checksum {
+        return Err(ParseErrorKind::ChecksumMismatch {
+            expected: expected_checksum,
+            found: found_checksum,
+        });
+    }
+
+    if let Some((body_length_value, tag9_delim)) = tag9 {
+        let declared_len =
+            parse_u64(body_length_value).map_err(|_| ParseErrorKind::InvalidNumber)? as usize;
+        let body_start = tag9_delim + 1;
+        let actual_len = tag10_start.saturating_sub(body_start);
+        if actual_len != declared_len {
+            return Err(ParseErrorKind::BodyLengthMismatch {
+                expected: declared_len,
+                found: actual_len,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Sum of every byte in `bytes`, mod 256 — the FIX checksum algorithm.
+fn checksum_of(bytes: &[u8]) -> u8 {
+    let mut sum: u32 = 0;
+    for &b in bytes {
+        sum = sum.wrapping_add(b as u32);
+    }
+    (sum & 0xFF) as u8
+}
+
 fn split_tag_value(field: &[u8]) -> Option<(&[u8], &[u8])> {
     // Split on the first '='. Values may contain '='; we preserve them.
     let mut idx = None;
@@ -342,13 +641,13 @@ fn parse_u64(mut s: &[u8]) -> Result<u64, ()> {
         return Err(());
     }
     // No escapes expected for numeric fields; if present, treat as invalid.
-    if s.iter().any(|&b| b == ESCAPE) {
+    if s.contains(&ESCAPE) {
         return Err(());
     }
     let mut v: u64 = 0;
     while !s.is_empty() {
         let d = s[0];
-        if !(b'0'..=b'9').contains(&d) {
+        if !d.is_ascii_digit() {
             return Err(());
         }
         v = v
@@ -369,7 +668,7 @@ fn parse_timestamp(s: &[u8]) -> Result<FixTimestamp, ()> {
     if s[8] != b'-' || s[11] != b':' || s[14] != b':' || s[17] != b'.' {
         return Err(());
     }
-    if s.iter().any(|&b| b == ESCAPE) {
+    if s.contains(&ESCAPE) {
         return Err(());
     }
 
@@ -394,19 +693,38 @@ pub struct SymbolSnapshot {
     pub symbol: String,
     pub count: u64,
     pub volume: u64,
+    pub p50: u64,
+    pub p95: u64,
+    pub p99: u64,
 }
 
+/// Fixed-capacity symbol interning: `TradeAnalyzer::new`'s `max_symbols` and
+/// `arena_bytes` size `slots` and `arena` once up front, so a previously
+/// unseen symbol is recorded into preallocated space rather than growing
+/// anything (see `tests/perf_and_alloc.rs`). This is the whole of the
+/// fixed-capacity-pool requirement; no separate pool type is layered on top.
 struct SymbolTable {
     mask: usize,
     slots: Box<[SymbolSlot]>,
     arena: Arena,
 }
 
+/// Sub-bucket resolution within each power-of-two exponent: each exponent is
+/// split into `1 << MANTISSA_BITS` equal-width sub-buckets keyed by the next
+/// most significant bits below the leading one.
+const MANTISSA_BITS: u32 = 2;
+/// One bucket per (exponent, mantissa) pair, for exponents `0..64`, plus the
+/// `qty == 0` edge case sharing bucket 0 (see [`bucket_for`]).
+const N_BUCKETS: usize = 64 * (1 << MANTISSA_BITS);
+
 struct SymbolSlot {
     key_hash: AtomicU64, // 0 => empty
     meta: AtomicU64,     // 0 => uninitialized
     count: AtomicU64,
     volume: AtomicU64,
+    /// Lock-free, fixed-size log-bucketed histogram of recorded quantities;
+    /// see [`bucket_for`] / [`Self::percentile`].
+    histogram: [AtomicU64; N_BUCKETS],
 }
 
 impl SymbolSlot {
@@ -416,10 +734,73 @@ impl SymbolSlot {
             meta: AtomicU64::new(0),
             count: AtomicU64::new(0),
             volume: AtomicU64::new(0),
+            histogram: std::array::from_fn(|_| AtomicU64::new(0)),
+        }
+    }
+
+    fn record_quantity(&self, qty: u64) {
+        self.histogram[bucket_for(qty)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Approximate `p`-th percentile (`0.0..=1.0`) of recorded quantities:
+    /// sums all bucket counts, then walks buckets in ascending order until
+    /// the cumulative count crosses `p * total`, returning that bucket's
+    /// representative lower-bound value.
+    fn percentile(&self, p: f64) -> u64 {
+        let total: u64 = self
+            .histogram
+            .iter()
+            .map(|b| b.load(Ordering::Relaxed))
+            .sum();
+        if total == 0 {
+            return 0;
+        }
+
+        let target = ((p * total as f64).ceil() as u64).max(1);
+        let mut cumulative = 0u64;
+        for (bucket, counter) in self.histogram.iter().enumerate() {
+            cumulative += counter.load(Ordering::Relaxed);
+            if cumulative >= target {
+                return bucket_lower_bound(bucket);
+            }
         }
+        bucket_lower_bound(N_BUCKETS - 1)
     }
 }
 
+/// Maps a recorded quantity to its histogram bucket: the coarse exponent is
+/// `63 - qty.leading_zeros()` (i.e. the position of the leading bit), refined
+/// by the next [`MANTISSA_BITS`] bits as a sub-bucket. `qty == 0` is clamped
+/// to bucket 0, which no positive quantity can otherwise reach (the smallest
+/// exponent, 0, needs a nonzero mantissa to be represented at all).
+/// Quantities below `1 << MANTISSA_BITS` have fewer than [`MANTISSA_BITS`]
+/// bits below their leading one, so their mantissa is the whole quantity.
+fn bucket_for(qty: u64) -> usize {
+    if qty == 0 {
+        return 0;
+    }
+    let exp = 63 - qty.leading_zeros();
+    let shift = exp.saturating_sub(MANTISSA_BITS);
+    let mantissa = ((qty >> shift) as usize) & ((1usize << MANTISSA_BITS) - 1);
+    ((exp as usize) << MANTISSA_BITS) | mantissa
+}
+
+/// Inverse of [`bucket_for`]: the smallest quantity that could have been
+/// counted in `bucket`.
+fn bucket_lower_bound(bucket: usize) -> u64 {
+    if bucket == 0 {
+        return 0;
+    }
+    let exp = (bucket >> MANTISSA_BITS) as u32;
+    let mantissa = (bucket & ((1 << MANTISSA_BITS) - 1)) as u64;
+    if exp < MANTISSA_BITS {
+        // `bucket_for` stores such quantities whole in the mantissa.
+        return mantissa;
+    }
+    let shift = exp - MANTISSA_BITS;
+    (1u64 << exp) + mantissa * (1u64 << shift)
+}
+
 impl SymbolTable {
     fn new(max_symbols: usize, arena_bytes: usize) -> Self {
         let cap = max_symbols.next_power_of_two().max(8);
@@ -449,6 +830,7 @@ impl SymbolTable {
                 if escaped_eq(symbol.raw(), stored) {
                     slot.count.fetch_add(1, Ordering::Relaxed);
                     slot.volume.fetch_add(qty, Ordering::Relaxed);
+                    slot.record_quantity(qty);
                     return Ok(());
                 }
                 // Extremely unlikely: hash collision. Continue probing for an empty slot.
@@ -469,6 +851,7 @@ impl SymbolTable {
                     slot.meta.store(pack_meta(off, decoded_len), Ordering::Release);
                     slot.count.store(1, Ordering::Relaxed);
                     slot.volume.store(qty, Ordering::Relaxed);
+                    slot.record_quantity(qty);
                     return Ok(());
                 }
             }
@@ -477,6 +860,35 @@ impl SymbolTable {
         Err(ParseErrorKind::TableFull)
     }
 
+    /// Finds the slot already recorded for `symbol`, if any. Unlike
+    /// [`Self::record`], this never claims an empty slot.
+    fn slot_for(&self, symbol: &[u8]) -> Option<&SymbolSlot> {
+        let (hash, _decoded_len) = hash64_and_len(symbol);
+        let mut idx = (hash as usize) & self.mask;
+
+        for _probe in 0..=self.mask {
+            let slot = &self.slots[idx];
+            let existing = slot.key_hash.load(Ordering::Acquire);
+            if existing == hash {
+                let meta = wait_meta(&slot.meta);
+                let (off, len) = unpack_meta(meta);
+                if let Some(stored) = self.arena.get(off, len) {
+                    if escaped_eq(symbol, stored) {
+                        return Some(slot);
+                    }
+                }
+            } else if existing == 0 {
+                return None;
+            }
+            idx = (idx + 1) & self.mask;
+        }
+        None
+    }
+
+    fn percentile(&self, symbol: &[u8], p: f64) -> Option<u64> {
+        Some(self.slot_for(symbol)?.percentile(p))
+    }
+
     fn snapshot(&self) -> Vec<SymbolSnapshot> {
         let mut out = Vec::new();
         for slot in self.slots.iter() {
@@ -501,6 +913,9 @@ impl SymbolTable {
                 symbol,
                 count,
                 volume,
+                p50: slot.percentile(0.50),
+                p95: slot.percentile(0.95),
+                p99: slot.percentile(0.99),
             });
         }
         out
@@ -571,6 +986,12 @@ impl Arena {
         Some(&self.buf[off..end])
     }
 
+    // `&self -> &mut [u8]` is exactly what clippy's `mut_from_ref` flags, but
+    // it's sound here: `off..end` always comes from `alloc`'s monotonic
+    // bump allocation, so distinct calls never produce overlapping regions,
+    // and each region is written exactly once (during insertion) before any
+    // `get`/`get_mut` of it is handed out.
+    #[allow(clippy::mut_from_ref)]
     fn get_mut(&self, off: usize, len: usize) -> Option<&mut [u8]> {
         let end = off.checked_add(len)?;
         if end > self.buf.len() {
@@ -0,0 +1,269 @@
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::groups::{FixMessage, Value};
+
+/// Fixed-point price representation (4 decimal places) so price levels can
+/// be used as `BTreeMap` keys without float `Ord` pitfalls.
+const PRICE_SCALE: i64 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+/// One resting price level: the decoded decimal price and the aggregated
+/// quantity resting there.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceLevel {
+    pub price: f64,
+    pub quantity: u64,
+}
+
+/// A resting order tracked so cancels (`41` OrigClOrdID), replaces, and
+/// execution reports (`11` ClOrdID) can find and adjust the price level
+/// they originally added.
+struct RestingOrder {
+    symbol: String,
+    side: Side,
+    price: i64,
+    qty_remaining: u64,
+}
+
+struct SymbolBook {
+    /// `Reverse` keys make the natural (ascending) `BTreeMap` iteration
+    /// order match best-bid-first (highest price first).
+    bids: BTreeMap<Reverse<i64>, u64>,
+    asks: BTreeMap<i64, u64>,
+}
+
+impl SymbolBook {
+    fn new() -> Self {
+        Self {
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+        }
+    }
+
+    fn add(&mut self, side: Side, price: i64, qty: u64) {
+        match side {
+            Side::Bid => *self.bids.entry(Reverse(price)).or_insert(0) += qty,
+            Side::Ask => *self.asks.entry(price).or_insert(0) += qty,
+        }
+    }
+
+    fn remove(&mut self, side: Side, price: i64, qty: u64) {
+        match side {
+            Side::Bid => remove_from_level(&mut self.bids, Reverse(price), qty),
+            Side::Ask => remove_from_level(&mut self.asks, price, qty),
+        }
+    }
+}
+
+fn remove_from_level<K: Ord + Copy>(levels: &mut BTreeMap<K, u64>, key: K, qty: u64) {
+    if let Some(resting) = levels.get_mut(&key) {
+        *resting = resting.saturating_sub(qty);
+        if *resting == 0 {
+            levels.remove(&key);
+        }
+    }
+}
+
+/// Price-level order book, aggregated per symbol, reconstructed from a
+/// stream of parsed [`FixMessage`]s.
+///
+/// - New order single (`35=D`) adds resting quantity at its price level.
+/// - Order cancel request (`35=F`) removes the referenced order's resting
+///   quantity, keyed by `OrigClOrdID` (41).
+/// - Order cancel/replace request (`35=G`) is a cancel of the original
+///   order followed by a new order at the replacement's price/quantity.
+/// - Execution report (`35=8`) decrements resting quantity by `LastQty`
+///   (32); `CumQty` (14) is cumulative and not needed for this incremental
+///   update.
+#[derive(Default)]
+pub struct OrderBook {
+    books: HashMap<String, SymbolBook>,
+    orders: HashMap<String, RestingOrder>,
+}
+
+impl OrderBook {
+    pub fn new() -> Self {
+        Self {
+            books: HashMap::new(),
+            orders: HashMap::new(),
+        }
+    }
+
+    /// Apply one parsed message's effect on the book. Messages that aren't
+    /// one of the handled `MsgType`s (`D`/`F`/`G`/`8`), or that are missing
+    /// a required field, are ignored.
+    pub fn apply(&mut self, msg: &FixMessage) {
+        // MsgType (35) is usually non-numeric ("D", "F", "G"), but some
+        // values (e.g. execution report, "8") parse as Value::Int — compare
+        // against a string either way.
+        let msg_type = match msg.field(35) {
+            Some(Value::Str(s)) => s.clone(),
+            Some(Value::Int(n)) => n.to_string(),
+            None => return,
+        };
+        match msg_type.as_str() {
+            "D" => self.on_new_order(msg),
+            "F" => self.on_cancel(msg),
+            "G" => {
+                self.on_cancel(msg);
+                self.on_new_order(msg);
+            }
+            "8" => self.on_execution_report(msg),
+            _ => {}
+        }
+    }
+
+    fn on_new_order(&mut self, msg: &FixMessage) {
+        let (Some(symbol), Some(side), Some(price_str), Some(qty), Some(cl_ord_id)) = (
+            field_str(msg, 55),
+            side_of(msg),
+            field_str(msg, 44),
+            field_u64(msg, 38),
+            field_str(msg, 11),
+        ) else {
+            return;
+        };
+        let Some(price) = parse_price(&price_str) else {
+            return;
+        };
+
+        self.books
+            .entry(symbol.to_string())
+            .or_insert_with(SymbolBook::new)
+            .add(side, price, qty);
+        self.orders.insert(
+            cl_ord_id.to_string(),
+            RestingOrder {
+                symbol: symbol.to_string(),
+                side,
+                price,
+                qty_remaining: qty,
+            },
+        );
+    }
+
+    fn on_cancel(&mut self, msg: &FixMessage) {
+        let Some(orig_id) = field_str(msg, 41) else {
+            return;
+        };
+        if let Some(order) = self.orders.remove(orig_id.as_ref()) {
+            if let Some(book) = self.books.get_mut(&order.symbol) {
+                book.remove(order.side, order.price, order.qty_remaining);
+            }
+        }
+    }
+
+    fn on_execution_report(&mut self, msg: &FixMessage) {
+        let (Some(cl_ord_id), Some(last_qty)) = (field_str(msg, 11), field_u64(msg, 32)) else {
+            return;
+        };
+        if let Some(order) = self.orders.get_mut(cl_ord_id.as_ref()) {
+            let filled = last_qty.min(order.qty_remaining);
+            order.qty_remaining -= filled;
+            if let Some(book) = self.books.get_mut(&order.symbol) {
+                book.remove(order.side, order.price, filled);
+            }
+            if order.qty_remaining == 0 {
+                self.orders.remove(cl_ord_id.as_ref());
+            }
+        }
+    }
+
+    /// The best resting bid and ask for `symbol`, or `None` if the symbol
+    /// has no book at all. Either side of the returned pair may itself be
+    /// `None` if that side currently has no resting quantity.
+    pub fn top_of_book(&self, symbol: &str) -> Option<(Option<PriceLevel>, Option<PriceLevel>)> {
+        let book = self.books.get(symbol)?;
+        let best_bid = book
+            .bids
+            .iter()
+            .next()
+            .map(|(Reverse(price), qty)| price_level(*price, *qty));
+        let best_ask = book
+            .asks
+            .iter()
+            .next()
+            .map(|(price, qty)| price_level(*price, *qty));
+        Some((best_bid, best_ask))
+    }
+
+    /// The top `n` price levels per side for `symbol`, best-first, or
+    /// `None` if the symbol has no book at all.
+    pub fn depth(&self, symbol: &str, n: usize) -> Option<(Vec<PriceLevel>, Vec<PriceLevel>)> {
+        let book = self.books.get(symbol)?;
+        let bids = book
+            .bids
+            .iter()
+            .take(n)
+            .map(|(Reverse(price), qty)| price_level(*price, *qty))
+            .collect();
+        let asks = book
+            .asks
+            .iter()
+            .take(n)
+            .map(|(price, qty)| price_level(*price, *qty))
+            .collect();
+        Some((bids, asks))
+    }
+}
+
+fn price_level(scaled_price: i64, qty: u64) -> PriceLevel {
+    PriceLevel {
+        price: scaled_price as f64 / PRICE_SCALE as f64,
+        quantity: qty,
+    }
+}
+
+fn side_of(msg: &FixMessage) -> Option<Side> {
+    match msg.field(54)? {
+        Value::Int(1) => Some(Side::Bid),
+        Value::Int(2) => Some(Side::Ask),
+        Value::Str(s) if s == "1" => Some(Side::Bid),
+        Value::Str(s) if s == "2" => Some(Side::Ask),
+        _ => None,
+    }
+}
+
+/// Reads `tag` as a string, regardless of whether it happened to parse as
+/// `Value::Int` (e.g. a purely numeric `ClOrdID`).
+fn field_str(msg: &FixMessage, tag: u16) -> Option<Cow<'_, str>> {
+    match msg.field(tag)? {
+        Value::Str(s) => Some(Cow::Borrowed(s.as_str())),
+        Value::Int(n) => Some(Cow::Owned(n.to_string())),
+    }
+}
+
+fn field_u64(msg: &FixMessage, tag: u16) -> Option<u64> {
+    match msg.field(tag)? {
+        Value::Int(n) => u64::try_from(*n).ok(),
+        Value::Str(s) => s.parse().ok(),
+    }
+}
+
+/// Parses a FIX decimal price string (e.g. `"150.25"`) into a fixed-point
+/// integer scaled by [`PRICE_SCALE`].
+fn parse_price(s: &str) -> Option<i64> {
+    let (neg, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let mut parts = s.splitn(2, '.');
+    let int_part: i64 = parts.next()?.parse().ok()?;
+    let mut frac = parts.next().unwrap_or("").to_string();
+    if frac.len() > 4 {
+        frac.truncate(4);
+    }
+    while frac.len() < 4 {
+        frac.push('0');
+    }
+    let frac_part: i64 = frac.parse().ok()?;
+    let total = int_part * PRICE_SCALE + frac_part;
+    Some(if neg { -total } else { total })
+}
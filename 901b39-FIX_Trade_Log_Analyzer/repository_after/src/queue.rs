@@ -0,0 +1,136 @@
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Error returned by [`FrameRing::push`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushFrameError {
+    /// No free slot was available.
+    Full,
+    /// `frame` is larger than every slot's fixed capacity.
+    TooLarge { max: usize, len: usize },
+}
+
+/// Lock-free bounded MPMC ring (Dmitry Vyukov's bounded MPMC queue
+/// algorithm — safe for MPSC use too) of *reusable* fixed-capacity byte
+/// slots. Capacity is rounded up to the next power of two, with a minimum
+/// of 2.
+///
+/// Unlike a ring buffer that moves a fresh heap-allocated buffer into a
+/// slot on every push, `FrameRing` pre-allocates each slot's buffer once
+/// at construction. `push` copies a frame's bytes into a free slot in
+/// place; `pop_with` hands the consumer a `&[u8]` borrowed from the slot
+/// and releases it for reuse once the callback returns. Steady-state
+/// ingestion is therefore allocation-free on both the producer and
+/// consumer side.
+pub struct FrameRing {
+    mask: usize,
+    max_frame_bytes: usize,
+    slots: Box<[FrameSlot]>,
+    enqueue_pos: AtomicUsize,
+    dequeue_pos: AtomicUsize,
+}
+
+struct FrameSlot {
+    /// Tracks which "generation" of the ring currently owns this slot, so
+    /// producers/consumers can tell a free slot from a ready one without a
+    /// separate lock.
+    sequence: AtomicUsize,
+    len: UnsafeCell<usize>,
+    buf: UnsafeCell<Box<[u8]>>,
+}
+
+// SAFETY: access to each slot's `len`/`buf` is gated by `sequence`, which
+// acts as the handoff between whichever producer/consumer currently owns it.
+unsafe impl Sync for FrameRing {}
+
+impl FrameRing {
+    /// `capacity` is rounded up to the next power of two (minimum 2); `max_frame_bytes`
+    /// bounds the size of any one frame a slot can hold.
+    pub fn new(capacity: usize, max_frame_bytes: usize) -> Self {
+        let cap = capacity.next_power_of_two().max(2);
+        let slots = (0..cap)
+            .map(|i| FrameSlot {
+                sequence: AtomicUsize::new(i),
+                len: UnsafeCell::new(0),
+                buf: UnsafeCell::new(vec![0u8; max_frame_bytes].into_boxed_slice()),
+            })
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+        Self {
+            mask: cap - 1,
+            max_frame_bytes,
+            slots,
+            enqueue_pos: AtomicUsize::new(0),
+            dequeue_pos: AtomicUsize::new(0),
+        }
+    }
+
+    /// Copies `frame` into a free slot without allocating.
+    pub fn push(&self, frame: &[u8]) -> Result<(), PushFrameError> {
+        if frame.len() > self.max_frame_bytes {
+            return Err(PushFrameError::TooLarge {
+                max: self.max_frame_bytes,
+                len: frame.len(),
+            });
+        }
+
+        let mut pos = self.enqueue_pos.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[pos & self.mask];
+            let seq = slot.sequence.load(Ordering::Acquire);
+            let diff = seq as isize - pos as isize;
+            if diff == 0 {
+                if self
+                    .enqueue_pos
+                    .compare_exchange_weak(pos, pos + 1, Ordering::Relaxed, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    unsafe {
+                        let buf: &mut [u8] = &mut *slot.buf.get();
+                        buf[..frame.len()].copy_from_slice(frame);
+                        *slot.len.get() = frame.len();
+                    }
+                    slot.sequence.store(pos + 1, Ordering::Release);
+                    return Ok(());
+                }
+                pos = self.enqueue_pos.load(Ordering::Relaxed);
+            } else if diff < 0 {
+                return Err(PushFrameError::Full);
+            } else {
+                pos = self.enqueue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Calls `f` with the oldest buffered frame's bytes and releases its
+    /// slot for reuse once `f` returns. `None` if the ring is currently
+    /// empty (`f` is not called).
+    pub fn pop_with<R>(&self, f: impl FnOnce(&[u8]) -> R) -> Option<R> {
+        let mut pos = self.dequeue_pos.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[pos & self.mask];
+            let seq = slot.sequence.load(Ordering::Acquire);
+            let diff = seq as isize - (pos + 1) as isize;
+            if diff == 0 {
+                if self
+                    .dequeue_pos
+                    .compare_exchange_weak(pos, pos + 1, Ordering::Relaxed, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    let result = unsafe {
+                        let len = *slot.len.get();
+                        let buf: &[u8] = &*slot.buf.get();
+                        f(&buf[..len])
+                    };
+                    slot.sequence.store(pos + self.mask + 1, Ordering::Release);
+                    return Some(result);
+                }
+                pos = self.dequeue_pos.load(Ordering::Relaxed);
+            } else if diff < 0 {
+                return None; // ring is empty
+            } else {
+                pos = self.dequeue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+}
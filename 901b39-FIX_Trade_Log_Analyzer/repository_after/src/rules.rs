@@ -0,0 +1,182 @@
+use std::borrow::Cow;
+
+use crate::groups::{FixMessage, Value};
+use crate::FixTimestamp;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub tag: Option<u16>,
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// A pluggable validation rule, analogous to a lint: it inspects one
+/// message and reports zero or more [`Diagnostic`]s. Implementations run on
+/// [`crate::TradeAnalyzer::process_message`]'s ingestion path only once at
+/// least one rule has been registered (see
+/// [`crate::TradeAnalyzer::register_rule`]) — with none registered, the
+/// hot path performs no extra work.
+pub trait FixRule {
+    fn check(&self, msg: &FixMessage) -> Vec<Diagnostic>;
+}
+
+/// Limit orders (`OrdType`/40 = `2`) must carry a positive `Price` (44).
+pub struct PriceMustBePositive;
+
+impl FixRule for PriceMustBePositive {
+    fn check(&self, msg: &FixMessage) -> Vec<Diagnostic> {
+        if field_str(msg, 40).as_deref() != Some("2") {
+            return Vec::new();
+        }
+        match field_f64(msg, 44) {
+            Some(price) if price <= 0.0 => vec![Diagnostic {
+                tag: Some(44),
+                severity: Severity::Error,
+                code: "price-not-positive",
+                message: format!("limit order Price (44) must be positive, got {price}"),
+            }],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// `OrderQty` (38) must be nonzero.
+pub struct QuantityNonZero;
+
+impl FixRule for QuantityNonZero {
+    fn check(&self, msg: &FixMessage) -> Vec<Diagnostic> {
+        match field_u64(msg, 38) {
+            Some(0) => vec![Diagnostic {
+                tag: Some(38),
+                severity: Severity::Error,
+                code: "quantity-zero",
+                message: "OrderQty (38) must be nonzero".to_string(),
+            }],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// `Side` (54) must be `1` (Buy) or `2` (Sell).
+pub struct SideValid;
+
+impl FixRule for SideValid {
+    fn check(&self, msg: &FixMessage) -> Vec<Diagnostic> {
+        match field_str(msg, 54).as_deref() {
+            Some("1") | Some("2") | None => Vec::new(),
+            Some(other) => vec![Diagnostic {
+                tag: Some(54),
+                severity: Severity::Error,
+                code: "side-invalid",
+                message: format!("Side (54) must be 1 or 2, got {other}"),
+            }],
+        }
+    }
+}
+
+/// `SendingTime` (52), if present, must not be after the reference time.
+pub struct TimestampNotInFuture {
+    now: FixTimestamp,
+}
+
+impl TimestampNotInFuture {
+    /// Uses the current wall-clock time as the reference.
+    pub fn now() -> Self {
+        Self {
+            now: unix_now_as_fix_timestamp(),
+        }
+    }
+
+    /// Uses a fixed reference time — useful for deterministic tests.
+    pub fn at(now: FixTimestamp) -> Self {
+        Self { now }
+    }
+}
+
+impl FixRule for TimestampNotInFuture {
+    fn check(&self, msg: &FixMessage) -> Vec<Diagnostic> {
+        let Some(raw) = field_str(msg, 52) else {
+            return Vec::new();
+        };
+        let Ok(ts) = crate::parse_fix_timestamp(raw.as_bytes()) else {
+            return Vec::new();
+        };
+        if ts.seconds > self.now.seconds {
+            vec![Diagnostic {
+                tag: Some(52),
+                severity: Severity::Warning,
+                code: "timestamp-in-future",
+                message: format!(
+                    "SendingTime (52) {} is after the reference time {}",
+                    ts.seconds, self.now.seconds
+                ),
+            }]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+fn field_str(msg: &FixMessage, tag: u16) -> Option<Cow<'_, str>> {
+    match msg.field(tag)? {
+        Value::Str(s) => Some(Cow::Borrowed(s.as_str())),
+        Value::Int(n) => Some(Cow::Owned(n.to_string())),
+    }
+}
+
+fn field_u64(msg: &FixMessage, tag: u16) -> Option<u64> {
+    match msg.field(tag)? {
+        Value::Int(n) => u64::try_from(*n).ok(),
+        Value::Str(s) => s.parse().ok(),
+    }
+}
+
+fn field_f64(msg: &FixMessage, tag: u16) -> Option<f64> {
+    match msg.field(tag)? {
+        Value::Int(n) => Some(*n as f64),
+        Value::Str(s) => s.parse().ok(),
+    }
+}
+
+/// Converts a Unix timestamp (seconds since epoch) into civil
+/// year/month/day/hour/minute/second, via Howard Hinnant's
+/// `civil_from_days` algorithm (public domain).
+fn civil_from_unix(unix_secs: u64) -> (i64, u32, u32, u32, u32, u32) {
+    let days = (unix_secs / 86_400) as i64;
+    let rem = (unix_secs % 86_400) as u32;
+    let (hh, mm, ss) = (rem / 3600, (rem % 3600) / 60, rem % 60);
+
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64; // [0, 146096]
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365; // [0, 399]
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // [0, 365]
+    let mp = (5 * doy + 2) / 153; // [0, 11]
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32; // [1, 31]
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32; // [1, 12]
+    let y = if m <= 2 { y + 1 } else { y };
+
+    (y, m, d, hh, mm, ss)
+}
+
+fn unix_now_as_fix_timestamp() -> FixTimestamp {
+    let unix_secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (y, m, d, hh, mm, ss) = civil_from_unix(unix_secs);
+    let ymd = (y as u64) * 10_000 + (m as u64) * 100 + (d as u64);
+    FixTimestamp {
+        seconds: ymd * 1_000_000 + (hh as u64) * 10_000 + (mm as u64) * 100 + (ss as u64),
+        micros: 0,
+    }
+}
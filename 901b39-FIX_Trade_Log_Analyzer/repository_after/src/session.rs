@@ -0,0 +1,228 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{find_unescaped, parse_u64, split_tag_value, FIELD_DELIM};
+
+/// Session-level action a driver should take in response to an inbound
+/// message, as decided by [`FixSession::next_action`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionAction {
+    /// The message was in sequence (or a recognized duplicate); no session
+    /// recovery is needed.
+    Accept,
+    /// A sequence gap was detected; the driver should send a `ResendRequest`
+    /// (`35=2`) covering `BeginSeqNo..=EndSeqNo`.
+    RequestResend { begin: u64, end: u64 },
+    /// The session is unrecoverable and should be torn down.
+    Disconnect { reason: DisconnectReason },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// The message couldn't be parsed well enough to extract the
+    /// session-level fields (tags `49`/`56`/`35`/`34`).
+    MalformedMessage,
+    /// Inbound `MsgSeqNum` (34) was lower than expected and `PossDupFlag`
+    /// (43) was not `Y`.
+    SequenceTooLow { expected: u64, received: u64 },
+}
+
+/// Expected inbound sequence-number state for one `(SenderCompID,
+/// TargetCompID)` pair.
+struct PeerState {
+    expected_seq: u64,
+    /// Out-of-order messages buffered while awaiting a gap fill/resend,
+    /// keyed by `MsgSeqNum`.
+    pending: BTreeMap<u64, Vec<u8>>,
+}
+
+impl PeerState {
+    fn new(expected_seq: u64) -> Self {
+        Self {
+            expected_seq,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Accepts the in-sequence message, then drains any contiguous buffered
+    /// messages that follow, advancing `expected_seq` for each and
+    /// returning the drained frames in sequence order.
+    fn accept_and_drain(&mut self) -> Vec<Vec<u8>> {
+        self.expected_seq += 1;
+        let mut drained = Vec::new();
+        while let Some(raw) = self.pending.remove(&self.expected_seq) {
+            drained.push(raw);
+            self.expected_seq += 1;
+        }
+        drained
+    }
+
+    /// Applies a `SequenceReset-GapFill`'s `NewSeqNo`, then drains any
+    /// buffered messages contiguous from that point, returning them in
+    /// sequence order.
+    fn catch_up_to(&mut self, new_seq_no: u64) -> Vec<Vec<u8>> {
+        self.expected_seq = new_seq_no;
+        let mut drained = Vec::new();
+        while let Some(raw) = self.pending.remove(&self.expected_seq) {
+            drained.push(raw);
+            self.expected_seq += 1;
+        }
+        drained
+    }
+}
+
+/// A FIX session layer above [`crate::TradeAnalyzer::process_message`]:
+/// tracks the expected inbound `MsgSeqNum` (tag 34) per `(SenderCompID,
+/// TargetCompID)` pair and enforces session-level semantics (gap detection,
+/// resend requests, gap fills, logon).
+///
+/// Unlike `TradeAnalyzer`, a session buffers out-of-order messages and is
+/// not part of the zero-allocation hot path.
+#[derive(Default)]
+pub struct FixSession {
+    peers: HashMap<(Vec<u8>, Vec<u8>), PeerState>,
+    /// Frames released by the most recent [`Self::next_action`] call because
+    /// a gap fill or in-sequence arrival made previously-buffered messages
+    /// contiguous. Drained by [`Self::drain_ready`].
+    ready: Vec<Vec<u8>>,
+}
+
+impl FixSession {
+    pub fn new() -> Self {
+        Self {
+            peers: HashMap::new(),
+            ready: Vec::new(),
+        }
+    }
+
+    /// Feed one inbound message and decide what the driver should do next.
+    ///
+    /// `raw` itself is the driver's to process when this returns `Accept`;
+    /// any out-of-order messages that were buffered and became contiguous
+    /// as a result of this call are **not** included in the return value —
+    /// call [`Self::drain_ready`] afterwards to retrieve and process them.
+    pub fn next_action(&mut self, raw: &[u8]) -> SessionAction {
+        self.ready.clear();
+
+        let fields = match SessionFields::parse(raw) {
+            Some(f) => f,
+            None => {
+                return SessionAction::Disconnect {
+                    reason: DisconnectReason::MalformedMessage,
+                }
+            }
+        };
+
+        let key = (fields.sender.to_vec(), fields.target.to_vec());
+
+        // Logon (re)initializes the session: the next expected message is
+        // whatever immediately follows this one.
+        if fields.msg_type == b"A" {
+            self.peers.insert(key, PeerState::new(fields.seq_num + 1));
+            return SessionAction::Accept;
+        }
+
+        let peer = self
+            .peers
+            .entry(key)
+            .or_insert_with(|| PeerState::new(fields.seq_num));
+
+        // SequenceReset-GapFill unconditionally advances past the gap.
+        if fields.msg_type == b"4" && fields.gap_fill {
+            if let Some(new_seq_no) = fields.new_seq_no {
+                self.ready = peer.catch_up_to(new_seq_no);
+                return SessionAction::Accept;
+            }
+        }
+
+        if fields.seq_num == peer.expected_seq {
+            self.ready = peer.accept_and_drain();
+            SessionAction::Accept
+        } else if fields.seq_num > peer.expected_seq {
+            let begin = peer.expected_seq;
+            let end = fields.seq_num - 1;
+            peer.pending.entry(fields.seq_num).or_insert_with(|| raw.to_vec());
+            SessionAction::RequestResend { begin, end }
+        } else if fields.poss_dup {
+            // A legitimate retransmit of an already-processed message.
+            SessionAction::Accept
+        } else {
+            SessionAction::Disconnect {
+                reason: DisconnectReason::SequenceTooLow {
+                    expected: peer.expected_seq,
+                    received: fields.seq_num,
+                },
+            }
+        }
+    }
+
+    /// Takes the frames released by the most recent [`Self::next_action`]
+    /// call — buffered out-of-order messages that became contiguous because
+    /// that call filled the gap ahead of them — for the driver to feed into
+    /// [`crate::TradeAnalyzer::process_message`] in order. Empty if nothing
+    /// was released. Calling this more than once per `next_action` call
+    /// returns the frames only the first time.
+    pub fn drain_ready(&mut self) -> Vec<Vec<u8>> {
+        std::mem::take(&mut self.ready)
+    }
+}
+
+/// The session-level fields this layer cares about, extracted from one raw
+/// message using the same `|`-delimited, backslash-escaped framing as
+/// [`crate::process_message`](crate::TradeAnalyzer::process_message).
+struct SessionFields<'a> {
+    sender: &'a [u8],
+    target: &'a [u8],
+    msg_type: &'a [u8],
+    seq_num: u64,
+    poss_dup: bool,
+    gap_fill: bool,
+    new_seq_no: Option<u64>,
+}
+
+impl<'a> SessionFields<'a> {
+    fn parse(raw: &'a [u8]) -> Option<Self> {
+        let mut sender = None;
+        let mut target = None;
+        let mut msg_type = None;
+        let mut seq_num = None;
+        let mut poss_dup = false;
+        let mut gap_fill = false;
+        let mut new_seq_no = None;
+
+        let mut i = 0usize;
+        while i < raw.len() {
+            let field_start = i;
+            let field_end = find_unescaped(raw, i, FIELD_DELIM);
+            i = match field_end {
+                Some(j) => j + 1,
+                None => raw.len(),
+            };
+            if field_start == i - 1 {
+                continue;
+            }
+
+            let field = &raw[field_start..field_end.unwrap_or(raw.len())];
+            let (tag, value) = split_tag_value(field)?;
+            match tag {
+                b"49" => sender = Some(value),
+                b"56" => target = Some(value),
+                b"35" => msg_type = Some(value),
+                b"34" => seq_num = Some(parse_u64(value).ok()?),
+                b"43" => poss_dup = value == b"Y",
+                b"123" => gap_fill = value == b"Y",
+                b"36" => new_seq_no = Some(parse_u64(value).ok()?),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            sender: sender?,
+            target: target?,
+            msg_type: msg_type?,
+            seq_num: seq_num?,
+            poss_dup,
+            gap_fill,
+            new_seq_no,
+        })
+    }
+}
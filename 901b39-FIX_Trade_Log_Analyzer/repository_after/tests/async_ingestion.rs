@@ -0,0 +1,92 @@
+use fix_trade_analyzer::{PushFrameError, TradeAnalyzer};
+use std::sync::Arc;
+use std::thread;
+
+const RAW: &[u8] =
+    b"8=FIX.4.2|35=D|49=SENDER|56=TARGET|11=ORD001|55=AAPL|54=1|38=100|44=150.25|52=20240115-09:30:00.123456|10=128|";
+
+#[test]
+fn push_does_not_apply_until_drain_worker_runs() {
+    let analyzer = TradeAnalyzer::new(1024, 4, 4096, 1 << 16, 0);
+    analyzer.push(RAW).unwrap();
+    assert_eq!(analyzer.total_messages(), 0);
+
+    let processed = analyzer.drain_worker();
+    assert_eq!(processed, 1);
+    assert_eq!(analyzer.total_messages(), 1);
+}
+
+#[test]
+fn drain_worker_processes_every_buffered_frame() {
+    let analyzer = TradeAnalyzer::new(1024, 8, 4096, 1 << 16, 0);
+    for _ in 0..5 {
+        analyzer.push(RAW).unwrap();
+    }
+    assert_eq!(analyzer.drain_worker(), 5);
+    assert_eq!(analyzer.total_messages(), 5);
+    // A second pass with nothing queued processes zero frames.
+    assert_eq!(analyzer.drain_worker(), 0);
+}
+
+#[test]
+fn push_returns_backpressure_when_queue_is_full() {
+    // Capacity rounds up to the next power of two, so use an exact one.
+    let analyzer = TradeAnalyzer::new(1024, 2, 4096, 1 << 16, 0);
+    analyzer.push(RAW).unwrap();
+    analyzer.push(RAW).unwrap();
+    assert_eq!(analyzer.push(RAW), Err(PushFrameError::Full));
+
+    // Draining frees capacity back up.
+    analyzer.drain_worker();
+    assert!(analyzer.push(RAW).is_ok());
+}
+
+#[test]
+fn push_rejects_a_frame_larger_than_the_slot_capacity() {
+    let analyzer = TradeAnalyzer::new(1024, 4, 16, 1 << 16, 0);
+    assert_eq!(
+        analyzer.push(RAW),
+        Err(PushFrameError::TooLarge {
+            max: 16,
+            len: RAW.len()
+        })
+    );
+}
+
+#[test]
+fn malformed_buffered_frame_is_counted_without_stopping_the_drain() {
+    let analyzer = TradeAnalyzer::new(1024, 8, 4096, 1 << 16, 0);
+    let malformed = b"8=FIX.4.2|35=D|49=SENDER|56=TARGET|11ORD001|55=AAPL|54=1|38=100|10=1|";
+    analyzer.push(malformed).unwrap();
+    analyzer.push(RAW).unwrap();
+
+    assert_eq!(analyzer.drain_worker(), 2);
+    assert_eq!(analyzer.total_messages(), 1);
+    assert_eq!(analyzer.malformed_messages(), 1);
+}
+
+#[test]
+fn push_from_multiple_producer_threads_is_all_drained() {
+    let analyzer = Arc::new(TradeAnalyzer::new(1024, 256, 4096, 1 << 16, 0));
+    let mut handles = Vec::new();
+    for _ in 0..8 {
+        let analyzer = Arc::clone(&analyzer);
+        handles.push(thread::spawn(move || {
+            for _ in 0..20 {
+                while analyzer.push(RAW).is_err() {
+                    // Bounded queue: retry until the consumer catches up.
+                    thread::yield_now();
+                }
+            }
+        }));
+    }
+    for h in handles {
+        h.join().unwrap();
+    }
+
+    let mut processed = 0;
+    while processed < 160 {
+        processed += analyzer.drain_worker();
+    }
+    assert_eq!(analyzer.total_messages(), 160);
+}
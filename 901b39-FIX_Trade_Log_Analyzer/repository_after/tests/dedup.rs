@@ -0,0 +1,37 @@
+use fix_trade_analyzer::TradeAnalyzer;
+
+const RAW: &[u8] =
+    b"8=FIX.4.2|35=D|49=SENDER|56=TARGET|11=ORD001|55=AAPL|54=1|38=100|44=150.25|52=20240115-09:30:00.123456|10=128|";
+
+const RAW_OTHER: &[u8] =
+    b"8=FIX.4.2|35=D|49=SENDER|56=TARGET|11=ORD002|55=MSFT|54=2|38=50|44=310.10|52=20240115-09:31:00.123456|10=129|";
+
+#[test]
+fn retransmitted_message_is_suppressed_and_counted() {
+    let analyzer = TradeAnalyzer::new(16, 64, 4096, 4096, 64);
+    analyzer.process_message(RAW).unwrap();
+    analyzer.process_message(RAW).unwrap();
+
+    assert_eq!(analyzer.total_messages(), 1);
+    assert_eq!(analyzer.duplicates_suppressed(), 1);
+}
+
+#[test]
+fn distinct_messages_are_not_treated_as_duplicates() {
+    let analyzer = TradeAnalyzer::new(16, 64, 4096, 4096, 64);
+    analyzer.process_message(RAW).unwrap();
+    analyzer.process_message(RAW_OTHER).unwrap();
+
+    assert_eq!(analyzer.total_messages(), 2);
+    assert_eq!(analyzer.duplicates_suppressed(), 0);
+}
+
+#[test]
+fn dedup_window_of_zero_disables_suppression() {
+    let analyzer = TradeAnalyzer::new(16, 64, 4096, 4096, 0);
+    analyzer.process_message(RAW).unwrap();
+    analyzer.process_message(RAW).unwrap();
+
+    assert_eq!(analyzer.total_messages(), 2);
+    assert_eq!(analyzer.duplicates_suppressed(), 0);
+}
@@ -0,0 +1,56 @@
+use fix_trade_analyzer::groups::{FixMessage, GroupRegistry, Value};
+
+#[test]
+fn parses_no_party_ids_repeating_group() {
+    let registry = GroupRegistry::with_defaults();
+    let raw = b"8=FIX.4.2|35=D|453=2|448=ABC|447=D|452=3|448=XYZ|447=D|452=1|58=ok|10=1|";
+    let msg = FixMessage::parse(raw, &registry).unwrap();
+
+    let parties = msg.group(453);
+    assert_eq!(parties.len(), 2);
+    assert_eq!(parties[0].get(&448), Some(&Value::Str("ABC".to_string())));
+    assert_eq!(parties[0].get(&447), Some(&Value::Str("D".to_string())));
+    assert_eq!(parties[0].get(&452), Some(&Value::Int(3)));
+    assert_eq!(parties[1].get(&448), Some(&Value::Str("XYZ".to_string())));
+    assert_eq!(parties[1].get(&452), Some(&Value::Int(1)));
+
+    // Fields outside the group are still parsed flat.
+    assert_eq!(msg.field(58), Some(&Value::Str("ok".to_string())));
+}
+
+#[test]
+fn parses_no_legs_repeating_group() {
+    let registry = GroupRegistry::with_defaults();
+    let raw = b"8=FIX.4.2|35=D|555=1|600=AAPL|601=5|10=1|";
+    let msg = FixMessage::parse(raw, &registry).unwrap();
+
+    let legs = msg.group(555);
+    assert_eq!(legs.len(), 1);
+    assert_eq!(legs[0].get(&600), Some(&Value::Str("AAPL".to_string())));
+    assert_eq!(legs[0].get(&601), Some(&Value::Int(5)));
+}
+
+#[test]
+fn message_without_a_group_returns_empty_slice() {
+    let registry = GroupRegistry::with_defaults();
+    let raw = b"8=FIX.4.2|35=D|55=AAPL|54=1|38=100|10=1|";
+    let msg = FixMessage::parse(raw, &registry).unwrap();
+
+    assert!(msg.group(453).is_empty());
+    assert_eq!(msg.field(55), Some(&Value::Str("AAPL".to_string())));
+}
+
+#[test]
+fn group_stops_at_declared_count_even_if_delimiter_tag_reappears() {
+    let registry = GroupRegistry::with_defaults();
+    // NoPartyIDs says 1, but the 448 tag appears twice: the second 448
+    // belongs to the message body again, not a third repetition.
+    let raw = b"8=FIX.4.2|35=D|453=1|448=ABC|447=D|452=3|448=EXTRA|10=1|";
+    let msg = FixMessage::parse(raw, &registry).unwrap();
+
+    let parties = msg.group(453);
+    assert_eq!(parties.len(), 1);
+    assert_eq!(parties[0].get(&448), Some(&Value::Str("ABC".to_string())));
+    // The extra 448 is treated as a flat, top-level field once the group closes.
+    assert_eq!(msg.field(448), Some(&Value::Str("EXTRA".to_string())));
+}
@@ -1,9 +1,9 @@
-use fix_trade_analyzer::{parse_fix_timestamp, FixTimestamp, TradeAnalyzer};
+use fix_trade_analyzer::{parse_fix_timestamp, FixTimestamp, ParseErrorKind, TradeAnalyzer};
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[test]
 fn parses_escaped_pipe_in_text_58() {
-    let analyzer = TradeAnalyzer::new(1024, 1 << 20);
+    let analyzer = TradeAnalyzer::new(1024, 1024, 4096, 1 << 20, 0);
     let raw = b"8=FIX.4.2|35=D|49=SENDER|56=TARGET|11=ORD001|55=AAPL|54=1|38=100|44=150.25|58=hello\\|world|52=20240115-09:30:00.123456|10=128|";
     analyzer.process_message(raw).unwrap();
     assert_eq!(analyzer.total_messages(), 1);
@@ -11,7 +11,7 @@ fn parses_escaped_pipe_in_text_58() {
 
 #[test]
 fn parses_order_id_containing_pipe() {
-    let analyzer = TradeAnalyzer::new(1024, 1 << 20);
+    let analyzer = TradeAnalyzer::new(1024, 1024, 4096, 1 << 20, 0);
     let raw = b"8=FIX.4.2|35=D|49=SENDER|56=TARGET|11=ORD\\|001|55=AAPL|54=1|38=100|44=150.25|58=ok|52=20240115-09:30:00.123456|10=128|";
     analyzer.process_message(raw).unwrap();
     assert_eq!(analyzer.total_messages(), 1);
@@ -19,7 +19,7 @@ fn parses_order_id_containing_pipe() {
 
 #[test]
 fn malformed_message_is_logged_and_skipped_without_crash() {
-    let analyzer = TradeAnalyzer::new(1024, 1 << 20);
+    let analyzer = TradeAnalyzer::new(1024, 1024, 4096, 1 << 20, 0);
     static ERR: AtomicUsize = AtomicUsize::new(0);
 
     // Missing '=' in one field (11ORD001) => InvalidField
@@ -46,9 +46,97 @@ fn timestamp_preserves_microsecond_precision() {
     );
 }
 
+#[test]
+fn percentile_tracks_order_size_distribution() {
+    let analyzer = TradeAnalyzer::new(16, 1024, 4096, 1 << 16, 0);
+    for qty in 1..=1000u64 {
+        let raw = format!(
+            "8=FIX.4.2|35=D|49=SENDER|56=TARGET|11=ORD{qty}|55=AAPL|54=1|38={qty}|44=1.0|58=ok|52=20240115-09:30:00.123456|10=1|"
+        );
+        analyzer.process_message(raw.as_bytes()).unwrap();
+    }
+
+    // The histogram is log-bucketed, so percentiles are approximate; check
+    // they land in the right order of magnitude rather than exact values.
+    let p50 = analyzer.percentile("AAPL", 0.50).unwrap();
+    let p95 = analyzer.percentile("AAPL", 0.95).unwrap();
+    let p99 = analyzer.percentile("AAPL", 0.99).unwrap();
+    assert!((400..=600).contains(&p50), "p50 = {p50}");
+    assert!((800..=1000).contains(&p95), "p95 = {p95}");
+    assert!(p99 >= p95, "p99 ({p99}) should be >= p95 ({p95})");
+}
+
+#[test]
+fn percentile_is_none_for_unknown_symbol() {
+    let analyzer = TradeAnalyzer::new(16, 1024, 4096, 1 << 16, 0);
+    assert_eq!(analyzer.percentile("MSFT", 0.5), None);
+}
+
+#[test]
+fn checked_accepts_a_message_with_correct_checksum_and_body_length() {
+    let analyzer = TradeAnalyzer::new(16, 1024, 4096, 1 << 16, 0);
+    let raw = b"8=FIX.4.2|9=99|35=D|49=SENDER|56=TARGET|11=ORD001|55=AAPL|54=1|38=100|44=150.25|58=ok|52=20240115-09:30:00.123456|10=058|";
+    analyzer.process_message_checked(raw).unwrap();
+    assert_eq!(analyzer.total_messages(), 1);
+    assert_eq!(analyzer.integrity_failures(), 0);
+}
+
+#[test]
+fn checked_rejects_a_tampered_checksum() {
+    let analyzer = TradeAnalyzer::new(16, 1024, 4096, 1 << 16, 0);
+    let raw = b"8=FIX.4.2|9=99|35=D|49=SENDER|56=TARGET|11=ORD001|55=AAPL|54=1|38=100|44=150.25|58=ok|52=20240115-09:30:00.123456|10=059|";
+    let err = analyzer.process_message_checked(raw).unwrap_err();
+    assert_eq!(
+        err.kind,
+        ParseErrorKind::ChecksumMismatch {
+            expected: 59,
+            found: 58
+        }
+    );
+    assert_eq!(analyzer.total_messages(), 0);
+}
+
+#[test]
+fn checked_rejects_a_tampered_body_length() {
+    let analyzer = TradeAnalyzer::new(16, 1024, 4096, 1 << 16, 0);
+    // BodyLength (9) claims 100 bytes but the body is actually 99.
+    let raw = b"8=FIX.4.2|9=100|35=D|49=SENDER|56=TARGET|11=ORD001|55=AAPL|54=1|38=100|44=150.25|58=ok|52=20240115-09:30:00.123456|10=089|";
+    let err = analyzer.process_message_checked(raw).unwrap_err();
+    assert_eq!(
+        err.kind,
+        ParseErrorKind::BodyLengthMismatch {
+            expected: 100,
+            found: 99
+        }
+    );
+}
+
+#[test]
+fn checked_lossy_counts_integrity_failures_separately_from_malformed() {
+    let analyzer = TradeAnalyzer::new(16, 1024, 4096, 1 << 16, 0);
+    static ERR: AtomicUsize = AtomicUsize::new(0);
+
+    // Tampered checksum: should bump integrity_failures, not malformed_messages.
+    let bad_checksum = b"8=FIX.4.2|9=99|35=D|49=SENDER|56=TARGET|11=ORD001|55=AAPL|54=1|38=100|44=150.25|58=ok|52=20240115-09:30:00.123456|10=059|";
+    analyzer.process_message_checked_lossy(bad_checksum, |_| {
+        ERR.fetch_add(1, Ordering::Relaxed);
+    });
+
+    // Structurally malformed (missing '='), but with a correct checksum:
+    // should bump malformed_messages, not integrity_failures.
+    let structurally_bad = b"8=FIX.4.2|35=D|49=SENDER|56=TARGET|11ORD001|55=AAPL|54=1|38=100|52=20240115-09:30:00.123456|10=077|";
+    analyzer.process_message_checked_lossy(structurally_bad, |_| {
+        ERR.fetch_add(1, Ordering::Relaxed);
+    });
+
+    assert_eq!(analyzer.integrity_failures(), 1);
+    assert_eq!(analyzer.malformed_messages(), 1);
+    assert_eq!(ERR.load(Ordering::Relaxed), 2);
+}
+
 #[test]
 fn concurrent_report_generation_does_not_block_ingestion() {
-    let analyzer = std::sync::Arc::new(TradeAnalyzer::new(1024, 1 << 20));
+    let analyzer = std::sync::Arc::new(TradeAnalyzer::new(1024, 1024, 4096, 1 << 20, 0));
     let raw = b"8=FIX.4.2|35=D|49=SENDER|56=TARGET|11=ORD001|55=AAPL|54=1|38=100|44=150.25|58=ok|52=20240115-09:30:00.123456|10=128|";
 
     let running = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
@@ -56,10 +144,18 @@ fn concurrent_report_generation_does_not_block_ingestion() {
     let a2 = analyzer.clone();
 
     let ingest = std::thread::spawn(move || {
+        // Structured as a do-while so at least one message is always
+        // processed before `running` is checked: report generation below can
+        // finish before this thread is even scheduled, and a plain
+        // `while running2.load(...) && ...` would then see `running == false`
+        // on its very first check and report zero processed messages.
         let mut n = 0u64;
-        while running2.load(Ordering::Relaxed) && n < 200_000 {
+        loop {
             a2.process_message(raw).unwrap();
             n += 1;
+            if !running2.load(Ordering::Relaxed) || n >= 200_000 {
+                break;
+            }
         }
         n
     });
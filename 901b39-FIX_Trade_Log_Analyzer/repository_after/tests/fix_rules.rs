@@ -0,0 +1,118 @@
+use fix_trade_analyzer::groups::{FixMessage, GroupRegistry};
+use fix_trade_analyzer::rules::{
+    FixRule, PriceMustBePositive, QuantityNonZero, SideValid, Severity, TimestampNotInFuture,
+};
+use fix_trade_analyzer::{FixTimestamp, TradeAnalyzer};
+
+fn check(rule: &dyn FixRule, raw: &[u8]) -> Vec<fix_trade_analyzer::rules::Diagnostic> {
+    let registry = GroupRegistry::with_defaults();
+    let msg = FixMessage::parse(raw, &registry).unwrap();
+    rule.check(&msg)
+}
+
+#[test]
+fn price_must_be_positive_flags_nonpositive_limit_price() {
+    let diags = check(
+        &PriceMustBePositive,
+        b"8=FIX.4.2|35=D|40=2|55=AAPL|54=1|38=100|44=-1.50|10=1|",
+    );
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].tag, Some(44));
+    assert_eq!(diags[0].severity, Severity::Error);
+    assert_eq!(diags[0].code, "price-not-positive");
+}
+
+#[test]
+fn price_must_be_positive_ignores_non_limit_orders() {
+    let diags = check(
+        &PriceMustBePositive,
+        b"8=FIX.4.2|35=D|40=1|55=AAPL|54=1|38=100|44=-1.50|10=1|",
+    );
+    assert!(diags.is_empty());
+}
+
+#[test]
+fn price_must_be_positive_accepts_positive_limit_price() {
+    let diags = check(
+        &PriceMustBePositive,
+        b"8=FIX.4.2|35=D|40=2|55=AAPL|54=1|38=100|44=150.25|10=1|",
+    );
+    assert!(diags.is_empty());
+}
+
+#[test]
+fn quantity_nonzero_flags_zero_quantity() {
+    let diags = check(
+        &QuantityNonZero,
+        b"8=FIX.4.2|35=D|55=AAPL|54=1|38=0|44=150.25|10=1|",
+    );
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].code, "quantity-zero");
+}
+
+#[test]
+fn side_valid_flags_invalid_side() {
+    let diags = check(&SideValid, b"8=FIX.4.2|35=D|55=AAPL|54=9|38=100|44=150.25|10=1|");
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].tag, Some(54));
+}
+
+#[test]
+fn side_valid_accepts_buy_and_sell() {
+    assert!(check(&SideValid, b"8=FIX.4.2|35=D|55=AAPL|54=1|38=100|44=150.25|10=1|").is_empty());
+    assert!(check(&SideValid, b"8=FIX.4.2|35=D|55=AAPL|54=2|38=100|44=150.25|10=1|").is_empty());
+}
+
+#[test]
+fn timestamp_not_in_future_flags_timestamp_after_reference() {
+    let reference = FixTimestamp {
+        seconds: 20_240_115_093_000,
+        micros: 0,
+    };
+    let rule = TimestampNotInFuture::at(reference);
+    let diags = check(
+        &rule,
+        b"8=FIX.4.2|35=D|55=AAPL|54=1|38=100|44=150.25|52=20240116-09:30:00.000000|10=1|",
+    );
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].code, "timestamp-in-future");
+}
+
+#[test]
+fn timestamp_not_in_future_accepts_past_timestamp() {
+    let reference = FixTimestamp {
+        seconds: 20_240_115_093_000,
+        micros: 0,
+    };
+    let rule = TimestampNotInFuture::at(reference);
+    let diags = check(
+        &rule,
+        b"8=FIX.4.2|35=D|55=AAPL|54=1|38=100|44=150.25|52=20240114-09:30:00.000000|10=1|",
+    );
+    assert!(diags.is_empty());
+}
+
+#[test]
+fn analyzer_collects_diagnostics_per_symbol_when_rules_registered() {
+    let analyzer = TradeAnalyzer::new(16, 64, 4096, 4096, 0);
+    analyzer.register_rule(Box::new(QuantityNonZero));
+    analyzer.register_rule(Box::new(SideValid));
+
+    let raw = b"8=FIX.4.2|35=D|49=S|56=T|11=ORD1|55=AAPL|54=1|38=0|44=150.25|52=20240115-09:30:00.000000|10=1|";
+    analyzer
+        .process_message(raw)
+        .expect("flat stats parsing still succeeds even though a rule flags it");
+
+    let diags = analyzer.diagnostics_for("AAPL");
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].code, "quantity-zero");
+    assert!(analyzer.diagnostics_for("MSFT").is_empty());
+}
+
+#[test]
+fn analyzer_without_registered_rules_collects_no_diagnostics() {
+    let analyzer = TradeAnalyzer::new(16, 64, 4096, 4096, 0);
+    let raw = b"8=FIX.4.2|35=D|49=S|56=T|11=ORD1|55=AAPL|54=1|38=0|44=150.25|52=20240115-09:30:00.000000|10=1|";
+    analyzer.process_message(raw).unwrap();
+    assert!(analyzer.diagnostics_for("AAPL").is_empty());
+}
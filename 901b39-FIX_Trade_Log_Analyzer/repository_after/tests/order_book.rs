@@ -0,0 +1,88 @@
+use fix_trade_analyzer::groups::{FixMessage, GroupRegistry};
+use fix_trade_analyzer::order_book::OrderBook;
+
+fn apply(book: &mut OrderBook, registry: &GroupRegistry, raw: &[u8]) {
+    book.apply(&FixMessage::parse(raw, registry).unwrap());
+}
+
+#[test]
+fn top_of_book_reflects_best_bid_and_ask() {
+    let registry = GroupRegistry::with_defaults();
+    let mut book = OrderBook::new();
+
+    apply(&mut book, &registry, b"8=FIX.4.2|35=D|11=B1|55=AAPL|54=1|38=100|44=150.25|10=1|");
+    apply(&mut book, &registry, b"8=FIX.4.2|35=D|11=B2|55=AAPL|54=1|38=50|44=150.00|10=1|");
+    apply(&mut book, &registry, b"8=FIX.4.2|35=D|11=A1|55=AAPL|54=2|38=75|44=151.00|10=1|");
+
+    let (bid, ask) = book.top_of_book("AAPL").unwrap();
+    let bid = bid.unwrap();
+    let ask = ask.unwrap();
+    assert!((bid.price - 150.25).abs() < 1e-9);
+    assert_eq!(bid.quantity, 100);
+    assert!((ask.price - 151.00).abs() < 1e-9);
+    assert_eq!(ask.quantity, 75);
+}
+
+#[test]
+fn execution_report_decrements_resting_quantity() {
+    let registry = GroupRegistry::with_defaults();
+    let mut book = OrderBook::new();
+
+    apply(&mut book, &registry, b"8=FIX.4.2|35=D|11=B1|55=AAPL|54=1|38=100|44=150.25|10=1|");
+    apply(&mut book, &registry, b"8=FIX.4.2|35=8|11=B1|55=AAPL|54=1|32=40|14=40|44=150.25|10=1|");
+
+    let (bid, _) = book.top_of_book("AAPL").unwrap();
+    assert_eq!(bid.unwrap().quantity, 60);
+}
+
+#[test]
+fn cancel_removes_resting_order() {
+    let registry = GroupRegistry::with_defaults();
+    let mut book = OrderBook::new();
+
+    apply(&mut book, &registry, b"8=FIX.4.2|35=D|11=B1|55=AAPL|54=1|38=100|44=150.25|10=1|");
+    apply(&mut book, &registry, b"8=FIX.4.2|35=F|41=B1|55=AAPL|54=1|44=150.25|10=1|");
+
+    let (bid, _) = book.top_of_book("AAPL").unwrap();
+    assert!(bid.is_none());
+}
+
+#[test]
+fn replace_moves_resting_quantity_to_new_price() {
+    let registry = GroupRegistry::with_defaults();
+    let mut book = OrderBook::new();
+
+    apply(&mut book, &registry, b"8=FIX.4.2|35=D|11=B1|55=AAPL|54=1|38=100|44=150.25|10=1|");
+    apply(
+        &mut book,
+        &registry,
+        b"8=FIX.4.2|35=G|41=B1|11=B2|55=AAPL|54=1|38=200|44=149.50|10=1|",
+    );
+
+    let (bids, _) = book.depth("AAPL", 5).unwrap();
+    assert_eq!(bids.len(), 1);
+    assert!((bids[0].price - 149.50).abs() < 1e-9);
+    assert_eq!(bids[0].quantity, 200);
+}
+
+#[test]
+fn depth_returns_levels_best_first_per_side() {
+    let registry = GroupRegistry::with_defaults();
+    let mut book = OrderBook::new();
+
+    apply(&mut book, &registry, b"8=FIX.4.2|35=D|11=B1|55=AAPL|54=1|38=10|44=100.00|10=1|");
+    apply(&mut book, &registry, b"8=FIX.4.2|35=D|11=B2|55=AAPL|54=1|38=20|44=101.00|10=1|");
+    apply(&mut book, &registry, b"8=FIX.4.2|35=D|11=A1|55=AAPL|54=2|38=5|44=102.00|10=1|");
+    apply(&mut book, &registry, b"8=FIX.4.2|35=D|11=A2|55=AAPL|54=2|38=7|44=103.00|10=1|");
+
+    let (bids, asks) = book.depth("AAPL", 5).unwrap();
+    assert_eq!(bids.iter().map(|l| l.price).collect::<Vec<_>>(), vec![101.0, 100.0]);
+    assert_eq!(asks.iter().map(|l| l.price).collect::<Vec<_>>(), vec![102.0, 103.0]);
+}
+
+#[test]
+fn unknown_symbol_has_no_book() {
+    let book = OrderBook::new();
+    assert!(book.top_of_book("MSFT").is_none());
+    assert!(book.depth("MSFT", 5).is_none());
+}
@@ -31,7 +31,7 @@ fn reset_alloc_counters() {
 
 #[test]
 fn hot_path_has_no_heap_allocations_after_warmup() {
-    let analyzer = TradeAnalyzer::new(16_384, 1 << 20);
+    let analyzer = TradeAnalyzer::new(16_384, 1024, 4096, 1 << 20, 0);
     let raw = b"8=FIX.4.2|35=D|49=SENDER|56=TARGET|11=ORD001|55=AAPL|54=1|38=100|44=150.25|58=hello\\|world|52=20240115-09:30:00.123456|10=128|";
 
     // Warmup: allow symbol insertion to occur.
@@ -52,11 +52,31 @@ fn hot_path_has_no_heap_allocations_after_warmup() {
     );
 }
 
+#[test]
+fn first_sighting_of_a_new_symbol_after_construction_does_not_allocate() {
+    // TradeAnalyzer::new preallocates its entire symbol table and arena up
+    // front from `max_symbols` and `arena_bytes`, so even a symbol never seen before should
+    // record without touching the heap.
+    let analyzer = TradeAnalyzer::new(16_384, 1024, 4096, 1 << 20, 0);
+    let raw = b"8=FIX.4.2|35=D|49=SENDER|56=TARGET|11=ORD001|55=GOOG|54=1|38=100|44=150.25|58=hello\\|world|52=20240115-09:30:00.123456|10=128|";
+
+    reset_alloc_counters();
+    analyzer.process_message(raw).unwrap();
+
+    let calls = ALLOC_CALLS.load(Ordering::Relaxed);
+    assert_eq!(
+        calls, 0,
+        "first sighting of a new symbol allocated (calls={}, bytes={})",
+        calls,
+        ALLOC_BYTES.load(Ordering::Relaxed)
+    );
+}
+
 #[test]
 fn processes_one_million_messages_under_three_seconds_release() {
     // This is a strict enforcement of the dataset requirement.
     // It assumes `cargo test --release` (the Dockerfile runs release tests).
-    let analyzer = TradeAnalyzer::new(16_384, 1 << 20);
+    let analyzer = TradeAnalyzer::new(16_384, 1024, 4096, 1 << 20, 0);
     let raw = b"8=FIX.4.2|35=D|49=SENDER|56=TARGET|11=ORD001|55=AAPL|54=1|38=100|44=150.25|58=hello\\|world|52=20240115-09:30:00.123456|10=128|";
 
     // Warmup